@@ -0,0 +1,47 @@
+use array_buf::ArrayDeque;
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+/// Queue usage: keeps `CAP / 2` elements, the indexes wrap many times.
+fn push_pop<const CAP: usize>() -> u64 {
+    let mut buf: ArrayDeque<u64, CAP> = ArrayDeque::new();
+    for i in 0..CAP as u64 / 2 {
+        buf.push_last(i).unwrap();
+    }
+    let mut sum = 0;
+    for i in 0..1024 {
+        buf.push_last(black_box(i)).unwrap();
+        sum += buf.pop_first().unwrap();
+    }
+    sum
+}
+
+/// Random access of the wrapped buffer.
+fn get_all<const CAP: usize>(buf: &ArrayDeque<u64, CAP>) -> u64 {
+    (0..buf.len()).map(|i| buf[i]).sum()
+}
+
+fn wrapped<const CAP: usize>() -> ArrayDeque<u64, CAP> {
+    let mut buf = ArrayDeque::new();
+    for _ in 0..CAP / 2 {
+        buf.push_last(0).unwrap();
+        buf.pop_first().unwrap();
+    }
+    for i in 0..CAP as u64 {
+        buf.push_last(i).unwrap();
+    }
+    buf
+}
+
+fn bench_capacity(c: &mut Criterion) {
+    let mut group = c.benchmark_group("capacity");
+    group.bench_function("push_pop/16", |b| b.iter(push_pop::<16>));
+    group.bench_function("push_pop/15", |b| b.iter(push_pop::<15>));
+    let (pow2, other) = (wrapped::<16>(), wrapped::<15>());
+    group.bench_function("get/16", |b| b.iter(|| get_all(black_box(&pow2))));
+    group.bench_function("get/15", |b| b.iter(|| get_all(black_box(&other))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_capacity);
+criterion_main!(benches);
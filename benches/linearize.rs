@@ -0,0 +1,51 @@
+use array_buf::ArrayDeque;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+const CAP: usize = 64;
+
+#[derive(Clone, Copy)]
+struct Large([u64; 32]);
+
+fn wrapped(start: usize) -> ArrayDeque<Large, CAP> {
+    let mut buf = ArrayDeque::new();
+    for _ in 0..start {
+        buf.push_last(Large([0; 32])).unwrap();
+        buf.pop_first().unwrap();
+    }
+    for i in 0..CAP {
+        buf.push_last(Large([i as u64; 32])).unwrap();
+    }
+    buf
+}
+
+fn bench_linearize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("linearize_large");
+    for start in [1, 8, 31, 32] {
+        group.bench_with_input(BenchmarkId::new("linearize", start), &start, |b, &start| {
+            b.iter_batched_ref(
+                || wrapped(start),
+                |buf| {
+                    buf.linearize();
+                    black_box(buf.first().map(|v| v.0[0]))
+                },
+                criterion::BatchSize::SmallInput,
+            )
+        });
+        group.bench_with_input(
+            BenchmarkId::new("slice_rotate_left", start),
+            &start,
+            |b, &start| {
+                b.iter_batched_ref(
+                    || [Large([1; 32]); CAP],
+                    |arr| black_box(arr).rotate_left(start),
+                    criterion::BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_linearize);
+criterion_main!(benches);
@@ -0,0 +1,35 @@
+use array_buf::ArrayDeque;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+fn filled<const CAP: usize>(len: usize) -> ArrayDeque<u64, CAP> {
+    let mut buf = ArrayDeque::new();
+    for i in 0..len as u64 {
+        buf.push_last(i).unwrap();
+    }
+    buf
+}
+
+/// The time per rotation doesn't depend on the length.
+fn bench_rotate_one(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rotate_one");
+    for len in [16, 255, 256] {
+        let mut buf = filled::<256>(len);
+        group.bench_with_input(BenchmarkId::new("rotate_left_one", len), &len, |b, _| {
+            b.iter(|| {
+                black_box(&mut buf).rotate_left_one();
+                black_box(buf.first().copied())
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("rotate_left_1", len), &len, |b, _| {
+            b.iter(|| {
+                black_box(&mut buf).rotate_left(1);
+                black_box(buf.first().copied())
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_rotate_one);
+criterion_main!(benches);
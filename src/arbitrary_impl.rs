@@ -0,0 +1,63 @@
+use crate::{ArrayDeque, ArrayDequePlain};
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+macro_rules! impl_arbitrary {
+    ($struct_name:ident $(< $($struct_gen:tt),* $(,)? >)?) => {
+        /// Generates a length in `0..=CAP`, and pushes each element to either end,
+        /// so the generated deque is not always contiguous.
+        #[cfg_attr(docsrs, doc(cfg(feature = "arbitrary")))]
+        impl<'a, T: $($($struct_gen +)*)? Arbitrary<'a>, const CAP: usize> Arbitrary<'a>
+            for $struct_name<T, CAP>
+        {
+            fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                let len = u.int_in_range(0..=CAP)?;
+                let mut buf = Self::new();
+                for _ in 0..len {
+                    let v = T::arbitrary(u)?;
+                    // `len <= CAP`, so it can't be full
+                    let pushed = if u.arbitrary()? {
+                        buf.push_first_within_capacity(v)
+                    } else {
+                        buf.push_last_within_capacity(v)
+                    };
+                    debug_assert!(pushed.is_ok());
+                }
+                Ok(buf)
+            }
+        }
+    };
+}
+
+impl_arbitrary!(ArrayDeque);
+impl_arbitrary!(ArrayDequePlain<Copy>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arbitrary() {
+        let data: Vec<u8> = (0..=255).cycle().take(4096).collect();
+        let mut u = Unstructured::new(&data);
+        let mut wrapped = false;
+        while !u.is_empty() {
+            let Ok(buf) = ArrayDeque::<u16, 8>::arbitrary(&mut u) else {
+                break;
+            };
+            assert!(buf.len() <= 8);
+            wrapped |= !buf.is_contiguous();
+        }
+        assert!(wrapped);
+
+        let mut u = Unstructured::new(&data[100..]);
+        let plain = ArrayDequePlain::<u8, 4>::arbitrary(&mut u).unwrap();
+        assert!(plain.len() <= 4);
+    }
+
+    #[test]
+    fn test_arbitrary_empty() {
+        let mut u = Unstructured::new(&[]);
+        let buf = ArrayDeque::<String, 4>::arbitrary(&mut u).unwrap();
+        assert!(buf.is_empty());
+    }
+}
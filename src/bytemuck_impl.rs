@@ -0,0 +1,81 @@
+use crate::ArrayDequePlain;
+use ::bytemuck::Pod;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+impl<T: Pod, const CAP: usize> ArrayDequePlain<T, CAP> {
+    /// Returns the bytes of the elements (in order), without copying.
+    ///
+    /// # Panics
+    ///
+    /// Must be contiguous (`as_slices().1` is empty). If it's not, use `linearize()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDequePlain;
+    ///
+    /// let mut buf: ArrayDequePlain<u16, 4> = ArrayDequePlain::new();
+    /// buf.push_last(0x0102).unwrap();
+    /// buf.push_last(0x0304).unwrap();
+    ///
+    /// assert_eq!(buf.as_bytes().len(), 4);
+    /// assert_eq!(buf.as_bytes()[..2], 0x0102u16.to_ne_bytes());
+    /// ```
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        let (first, second) = self.as_slices();
+        assert!(second.is_empty(), "deque must be contiguous");
+        ::bytemuck::cast_slice(first)
+    }
+
+    /// Returns the mutable bytes of the elements (in order), without copying.
+    ///
+    /// # Panics
+    ///
+    /// Must be contiguous (`as_slices().1` is empty). If it's not, use `linearize()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDequePlain;
+    ///
+    /// let mut buf: ArrayDequePlain<u16, 4> = ArrayDequePlain::new();
+    /// buf.push_last(0).unwrap();
+    /// buf.as_bytes_mut().copy_from_slice(&7u16.to_ne_bytes());
+    ///
+    /// assert_eq!(buf.first(), Some(&7));
+    /// ```
+    #[inline]
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        let (first, second) = self.as_mut_slices();
+        assert!(second.is_empty(), "deque must be contiguous");
+        ::bytemuck::cast_slice_mut(first)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_as_bytes_wrapped() {
+        let mut buf: ArrayDequePlain<u32, 4> = ArrayDequePlain::new();
+        buf.push_last(2).unwrap();
+        buf.push_first(1).unwrap();
+        assert!(!buf.is_contiguous());
+        assert!(std::panic::catch_unwind(|| buf.as_bytes().len()).is_err());
+
+        buf.linearize();
+        let bytes: Vec<u8> = [1u32, 2].iter().flat_map(|v| v.to_ne_bytes()).collect();
+        assert_eq!(buf.as_bytes(), bytes);
+
+        // full and starting at 0 is in order
+        buf.push_last(3).unwrap();
+        buf.push_last(4).unwrap();
+        assert!(buf.is_full());
+        assert_eq!(buf.as_bytes().len(), 16);
+
+        let empty: ArrayDequePlain<u32, 4> = ArrayDequePlain::new();
+        assert!(empty.as_bytes().is_empty());
+    }
+}
@@ -0,0 +1,175 @@
+use crate::{ArrayDeque, ArrayDequePlain};
+use ::bytes::{Buf, BufMut, Bytes, BytesMut, buf::UninitSlice};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+impl<const CAP: usize> ArrayDequePlain<u8, CAP> {
+    /// Copies the bytes (in order) into a new `Bytes`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDequePlain;
+    ///
+    /// let mut buf: ArrayDequePlain<u8, 4> = ArrayDequePlain::new();
+    /// buf.push_last(b'c').unwrap();
+    /// buf.push_first(b'b').unwrap();
+    /// buf.push_first(b'a').unwrap();
+    ///
+    /// assert_eq!(buf.to_bytes(), &b"abc"[..]);
+    /// ```
+    #[inline]
+    pub fn to_bytes(&self) -> Bytes {
+        let (first, second) = self.as_slices();
+        let mut bytes = BytesMut::with_capacity(first.len() + second.len());
+        bytes.extend_from_slice(first);
+        bytes.extend_from_slice(second);
+        bytes.freeze()
+    }
+
+    /// Copies only the first contiguous run of bytes (`as_slices().0`) into a new `Bytes`.
+    /// It contains all the bytes if the buffer is not wrapped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDequePlain;
+    ///
+    /// let mut buf: ArrayDequePlain<u8, 4> = ArrayDequePlain::new();
+    /// buf.push_last(b'b').unwrap();
+    ///
+    /// assert_eq!(buf.first_run_bytes(), &b"b"[..]);
+    ///
+    /// buf.push_first(b'a').unwrap();
+    ///
+    /// assert_eq!(buf.first_run_bytes(), &b"a"[..]);
+    /// ```
+    #[inline]
+    pub fn first_run_bytes(&self) -> Bytes {
+        Bytes::copy_from_slice(self.as_slices().0)
+    }
+}
+
+macro_rules! impl_buf {
+    ($struct_name:ident) => {
+        /// Reads the bytes from the front, `chunk()` returns the first contiguous run (`as_slices().0`).
+        #[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+        impl<const CAP: usize> Buf for $struct_name<u8, CAP> {
+            #[inline(always)]
+            fn remaining(&self) -> usize {
+                self.len()
+            }
+
+            #[inline(always)]
+            fn chunk(&self) -> &[u8] {
+                self.as_slices().0
+            }
+
+            #[inline]
+            fn advance(&mut self, cnt: usize) {
+                let len = self.len();
+                assert!(
+                    cnt <= len,
+                    "cannot advance past `remaining`: {cnt} <= {len}"
+                );
+                self.drain(..cnt);
+            }
+        }
+
+        /// Writes the bytes to the back, `chunk_mut()` returns the first contiguous run
+        /// of the spare capacity (`spare_capacity_mut().0`).
+        #[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+        unsafe impl<const CAP: usize> BufMut for $struct_name<u8, CAP> {
+            #[inline(always)]
+            fn remaining_mut(&self) -> usize {
+                self.remaining_capacity()
+            }
+
+            #[inline]
+            fn chunk_mut(&mut self) -> &mut UninitSlice {
+                let run = self.spare_capacity_mut().0;
+                unsafe { UninitSlice::from_raw_parts_mut(run.as_mut_ptr().cast(), run.len()) }
+            }
+
+            #[inline]
+            unsafe fn advance_mut(&mut self, cnt: usize) {
+                let remaining = self.remaining_capacity();
+                assert!(
+                    cnt <= remaining,
+                    "cannot advance past `remaining_mut`: {cnt} <= {remaining}"
+                );
+                unsafe { self.set_len_back(cnt) };
+            }
+        }
+    };
+}
+
+impl_buf!(ArrayDeque);
+impl_buf!(ArrayDequePlain);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let mut buf: ArrayDequePlain<u8, 8> = ArrayDequePlain::new();
+        for b in 0..5 {
+            buf.push_last(b).unwrap();
+        }
+        for b in [12, 11, 10] {
+            buf.push_first(b).unwrap();
+        }
+        assert!(!buf.is_contiguous());
+
+        let bytes = buf.to_bytes();
+        let other: ArrayDequePlain<u8, 8> = bytes.iter().collect();
+        assert_eq!(bytes, &[10, 11, 12, 0, 1, 2, 3, 4][..]);
+        assert_eq!(other.as_slices().0, &bytes[..]);
+        assert_eq!(buf.first_run_bytes(), &bytes[..3]);
+    }
+
+    #[test]
+    fn test_buf_copy_to_bytes_wrapped() {
+        let mut buf: ArrayDeque<u8, 8> = ArrayDeque::new();
+        for b in 0..5 {
+            buf.push_last(b).unwrap();
+        }
+        for b in [12, 11, 10] {
+            buf.push_first(b).unwrap();
+        }
+        assert_eq!(buf.chunk(), [10, 11, 12]);
+
+        buf.advance(1);
+        assert_eq!(buf.copy_to_bytes(4), &[11, 12, 0, 1][..]);
+        assert_eq!(buf.remaining(), 3);
+        assert_eq!(buf.get_u16(), 0x0203);
+        assert_eq!(buf.get_u8(), 4);
+        assert!(!buf.has_remaining());
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot advance past `remaining`")]
+    fn test_buf_advance_past_remaining() {
+        let mut buf: ArrayDequePlain<u8, 4> = ArrayDequePlain::new();
+        buf.push_last(1).unwrap();
+        buf.advance(2);
+    }
+
+    #[test]
+    fn test_buf_mut_wrapped() {
+        let mut buf: ArrayDequePlain<u8, 8> = ArrayDequePlain::new();
+        buf.put_slice(&[0; 6]);
+        buf.advance(5);
+        assert_eq!(buf.remaining_mut(), 7);
+        assert_eq!(buf.chunk_mut().len(), 2);
+
+        // the spare capacity wraps
+        buf.put_u32(0x01020304);
+        buf.put_slice(b"ab");
+        assert_eq!(buf.as_slices(), (&[0, 1, 2][..], &[3, 4, b'a', b'b'][..]));
+        assert_eq!(buf.remaining_mut(), 1);
+        buf.put_u8(b'c');
+        assert!(buf.is_full());
+        assert_eq!(buf.chunk_mut().len(), 0);
+    }
+}
@@ -1,10 +1,22 @@
-use ::core::{fmt::Debug, mem::MaybeUninit, ptr};
+use crate::{CapacityError, Drain, ExtractIf, IntoIter, Iter, IterMut, PushError};
+use ::core::{
+    cmp::Ordering,
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+    mem::{self, ManuallyDrop, MaybeUninit},
+    ops::{Bound, Index, IndexMut, Range, RangeBounds},
+    ptr, slice,
+};
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, collections::VecDeque, vec::Vec};
 
 pub(crate) struct ArrayDequeBase<T, const CAP: usize> {
     arr: [MaybeUninit<T>; CAP],
     start: usize,
     end: usize,
     full: bool,
+    /// Push overwrites when full.
+    overwrite: bool,
 }
 
 impl<T: Debug, const CAP: usize> Debug for ArrayDequeBase<T, CAP> {
@@ -14,6 +26,7 @@ impl<T: Debug, const CAP: usize> Debug for ArrayDequeBase<T, CAP> {
             .field("start", &self.start)
             .field("end", &self.end)
             .field("full", &self.full)
+            .field("overwrite", &self.overwrite)
             .finish()
     }
 }
@@ -44,6 +57,7 @@ impl<T: Clone, const CAP: usize> Clone for ArrayDequeBase<T, CAP> {
             start: self.start,
             end: self.end,
             full: self.full,
+            overwrite: self.overwrite,
         }
     }
 }
@@ -56,9 +70,37 @@ impl<T, const CAP: usize> Default for ArrayDequeBase<T, CAP> {
     }
 }
 
+impl<T, const CAP: usize> Index<usize> for ArrayDequeBase<T, CAP> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        let len = self.len();
+        assert!(
+            index < len,
+            "index out of bounds: the len is {len} but the index is {index}"
+        );
+        unsafe { self.get_unchecked(index) }
+    }
+}
+
+impl<T, const CAP: usize> IndexMut<usize> for ArrayDequeBase<T, CAP> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len();
+        assert!(
+            index < len,
+            "index out of bounds: the len is {len} but the index is {index}"
+        );
+        unsafe { self.get_unchecked_mut(index) }
+    }
+}
+
 impl<T, const CAP: usize> ArrayDequeBase<T, CAP> {
-    /// Bits
+    /// Bits, if `CAP` is a power of two.
     const MAX_IDX: usize = CAP - 1;
+    /// Size of an element above which `linearize` uses the cycle rotation.
+    const LARGE_SIZE: usize = 128;
 
     /// Creates an empty `ArrayDeque`.
     ///
@@ -71,13 +113,13 @@ impl<T, const CAP: usize> ArrayDequeBase<T, CAP> {
     /// ```
     #[inline]
     pub const fn new() -> Self {
-        const { assert!(CAP > 1) };
-        const { assert!(CAP.is_power_of_two()) };
+        const { assert!(CAP > 0) };
         Self {
             arr: unsafe { MaybeUninit::uninit().assume_init() },
             start: 0,
             end: 0,
             full: false,
+            overwrite: false,
         }
     }
 
@@ -97,6 +139,117 @@ impl<T, const CAP: usize> ArrayDequeBase<T, CAP> {
         CAP
     }
 
+    /// Rotates the whole array, moving each slot once (and one temp per cycle).
+    ///
+    /// # Safety
+    ///
+    /// `0 < k < CAP`.
+    #[inline]
+    unsafe fn rotate_arr_left_cycles(&mut self, k: usize) {
+        debug_assert!(k > 0 && k < CAP);
+
+        let cycles = if CAP.is_power_of_two() {
+            // `gcd(k, CAP)` is the lowest set bit of `k`
+            k & k.wrapping_neg()
+        } else {
+            let (mut a, mut b) = (CAP, k);
+            while b != 0 {
+                (a, b) = (b, a % b);
+            }
+            a
+        };
+        let arr = self.arr.as_mut_ptr();
+        for c in 0..cycles {
+            unsafe {
+                let tmp = ptr::read(arr.add(c));
+                let mut j = c;
+                loop {
+                    let next = Self::wrap_add(j, k);
+                    if next == c {
+                        break;
+                    }
+                    ptr::copy_nonoverlapping(arr.add(next), arr.add(j), 1);
+                    j = next;
+                }
+                ptr::write(arr.add(j), tmp);
+            }
+        }
+    }
+
+    /// `(a + b) % CAP`, where `a < CAP` and `b <= CAP`.
+    ///
+    /// A bit mask if `CAP` is a power of two, otherwise a conditional subtraction
+    /// (the branch is resolved at compile time).
+    #[inline(always)]
+    const fn wrap_add(a: usize, b: usize) -> usize {
+        if CAP.is_power_of_two() {
+            a.wrapping_add(b) & Self::MAX_IDX
+        } else {
+            let i = a + b;
+            if i >= CAP { i - CAP } else { i }
+        }
+    }
+
+    /// `(a - b) % CAP`, where `a < CAP` and `b <= CAP`.
+    #[inline(always)]
+    const fn wrap_sub(a: usize, b: usize) -> usize {
+        if CAP.is_power_of_two() {
+            a.wrapping_sub(b) & Self::MAX_IDX
+        } else if a >= b {
+            a - b
+        } else {
+            a + (CAP - b)
+        }
+    }
+
+    /// Physical index of the logical `index`.
+    #[inline(always)]
+    fn idx(&self, index: usize) -> usize {
+        Self::wrap_add(self.start, index)
+    }
+
+    /// Bitwise copies the element at the logical `src` index to the logical `dst` index.
+    ///
+    /// # Safety
+    ///
+    /// `src` must be initialized, the old `dst` value is overwritten without dropping,
+    /// and `src` must be treated as uninitialized afterwards.
+    #[inline(always)]
+    pub(crate) unsafe fn copy_logical(&mut self, src: usize, dst: usize) {
+        let arr = self.arr.as_mut_ptr();
+        unsafe { ptr::copy_nonoverlapping(arr.add(self.idx(src)), arr.add(self.idx(dst)), 1) };
+    }
+
+    /// Sets the number of elements, keeping the start.
+    ///
+    /// # Safety
+    ///
+    /// `len` must not be greater than `CAP`, and the first `len` logical slots must be initialized.
+    #[inline(always)]
+    pub(crate) unsafe fn set_len(&mut self, len: usize) {
+        debug_assert!(len <= CAP);
+
+        self.end = self.idx(len);
+        self.full = len == CAP;
+    }
+
+    /// Pointer to the slot of the logical `index`, which may be outside of `len()`.
+    #[inline(always)]
+    pub(crate) fn logical_ptr(&mut self, index: usize) -> *mut T {
+        let idx = self.idx(index);
+        unsafe { self.arr.get_unchecked_mut(idx).as_mut_ptr() }
+    }
+
+    /// Reads the value at the logical `index`, which may be outside of `len()`.
+    ///
+    /// # Safety
+    ///
+    /// The slot must be initialized, and treated as uninitialized afterwards.
+    #[inline(always)]
+    pub(crate) unsafe fn read_logical(&mut self, index: usize) -> T {
+        unsafe { self.logical_ptr(index).read() }
+    }
+
     /// Returns the number of elements in the array.
     ///
     /// # Examples
@@ -127,7 +280,7 @@ impl<T, const CAP: usize> ArrayDequeBase<T, CAP> {
         if self.full {
             self.capacity()
         } else {
-            self.end.wrapping_sub(self.start) & Self::MAX_IDX
+            Self::wrap_sub(self.end, self.start)
         }
     }
 
@@ -189,7 +342,7 @@ impl<T, const CAP: usize> ArrayDequeBase<T, CAP> {
         debug_assert!(!self.is_empty());
 
         let val = unsafe { self.arr.get_unchecked(self.start).assume_init_read() };
-        self.start = self.start.wrapping_add(1) & Self::MAX_IDX;
+        self.start = Self::wrap_add(self.start, 1);
         self.full = false;
         val
     }
@@ -201,7 +354,7 @@ impl<T, const CAP: usize> ArrayDequeBase<T, CAP> {
     pub unsafe fn pop_last_unchecked(&mut self) -> T {
         debug_assert!(!self.is_empty());
 
-        self.end = self.end.wrapping_sub(1) & Self::MAX_IDX;
+        self.end = Self::wrap_sub(self.end, 1);
         self.full = false;
         unsafe { self.arr.get_unchecked(self.end).assume_init_read() }
     }
@@ -263,7 +416,7 @@ impl<T, const CAP: usize> ArrayDequeBase<T, CAP> {
     pub unsafe fn push_first_unchecked(&mut self, element: T) {
         debug_assert!(!self.is_full());
 
-        self.start = self.start.wrapping_sub(1) & Self::MAX_IDX;
+        self.start = Self::wrap_sub(self.start, 1);
         let val = unsafe { self.arr.get_unchecked_mut(self.start) };
         *val = MaybeUninit::new(element);
         self.full = self.start == self.end;
@@ -278,13 +431,14 @@ impl<T, const CAP: usize> ArrayDequeBase<T, CAP> {
 
         let val = unsafe { self.arr.get_unchecked_mut(self.end) };
         *val = MaybeUninit::new(element);
-        self.end = self.end.wrapping_add(1) & Self::MAX_IDX;
+        self.end = Self::wrap_add(self.end, 1);
         self.full = self.start == self.end;
     }
 
     /// Add an element to the start of the deque.
     ///
-    /// Return `Ok` if the push succeeds, or `Err` if the array is full.
+    /// Return `Ok` if the push succeeds, or `Err` with the element if the array is full.
+    /// In the overwrite mode (see `set_overwrite()`) drops the last element instead.
     ///
     /// # Examples
     ///
@@ -298,21 +452,22 @@ impl<T, const CAP: usize> ArrayDequeBase<T, CAP> {
     ///
     /// let overflow = buf.push_first(-3);
     ///
-    /// assert!(overflow.is_err());
+    /// assert_eq!(overflow.unwrap_err().into_inner(), -3);
     /// assert_eq!(buf.first(), Some(&-2));
     /// ```
     #[inline]
-    pub fn push_first(&mut self, element: T) -> Result<(), &'static str> {
-        if self.is_full() {
-            return Err("array is full");
+    pub fn push_first(&mut self, element: T) -> Result<(), CapacityError<T>> {
+        if self.is_full() && self.overwrite {
+            drop(unsafe { self.pop_last_unchecked() });
         }
-        unsafe { self.push_first_unchecked(element) };
-        Ok(())
+        self.push_first_within_capacity(element)
+            .map_err(CapacityError)
     }
 
     /// Add an element to the end of the deque.
     ///
-    /// Return `Ok` if the push succeeds, or `Err` if the array is full.
+    /// Return `Ok` if the push succeeds, or `Err` with the element if the array is full.
+    /// In the overwrite mode (see `set_overwrite()`) drops the first element instead.
     ///
     /// # Examples
     ///
@@ -326,16 +481,16 @@ impl<T, const CAP: usize> ArrayDequeBase<T, CAP> {
     ///
     /// let overflow = buf.push_last(3);
     ///
-    /// assert!(overflow.is_err());
+    /// assert_eq!(overflow.unwrap_err().into_inner(), 3);
     /// assert_eq!(buf.last(), Some(&2));
     /// ```
     #[inline]
-    pub fn push_last(&mut self, element: T) -> Result<(), &'static str> {
-        if self.is_full() {
-            return Err("array is full");
+    pub fn push_last(&mut self, element: T) -> Result<(), CapacityError<T>> {
+        if self.is_full() && self.overwrite {
+            drop(unsafe { self.pop_first_unchecked() });
         }
-        unsafe { self.push_last_unchecked(element) };
-        Ok(())
+        self.push_last_within_capacity(element)
+            .map_err(CapacityError)
     }
 
     /// Provides a reference to the first element, or `None` if empty.
@@ -406,7 +561,7 @@ impl<T, const CAP: usize> ArrayDequeBase<T, CAP> {
         if self.is_empty() {
             None
         } else {
-            let idx = self.end.wrapping_sub(1) & Self::MAX_IDX;
+            let idx = Self::wrap_sub(self.end, 1);
             Some(unsafe { self.arr.get_unchecked(idx).assume_init_ref() })
         }
     }
@@ -431,7 +586,7 @@ impl<T, const CAP: usize> ArrayDequeBase<T, CAP> {
         if self.is_empty() {
             None
         } else {
-            let idx = self.end.wrapping_sub(1) & Self::MAX_IDX;
+            let idx = Self::wrap_sub(self.end, 1);
             Some(unsafe { self.arr.get_unchecked_mut(idx).assume_init_mut() })
         }
     }
@@ -563,6 +718,47 @@ impl<T, const CAP: usize> ArrayDequeBase<T, CAP> {
         }
     }
 
+    /// Returns the contents as a contiguous slice, borrowed if the buffer
+    /// is not wrapped, otherwise cloned into an owned `Vec`.
+    ///
+    /// Unlike `linearize()`, doesn't require `&mut self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    /// use std::borrow::Cow;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    ///
+    /// buf.push_last(1).unwrap();
+    /// buf.push_last(2).unwrap();
+    ///
+    /// assert!(matches!(buf.as_contiguous_cow(), Cow::Borrowed(&[1, 2])));
+    ///
+    /// buf.push_first(-1).unwrap();
+    ///
+    /// assert!(matches!(buf.as_contiguous_cow(), Cow::Owned(_)));
+    /// assert_eq!(buf.as_contiguous_cow(), &[-1, 1, 2][..]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn as_contiguous_cow(&self) -> Cow<'_, [T]>
+    where
+        T: Clone,
+    {
+        let (first, second) = self.as_slices();
+        if second.is_empty() {
+            Cow::Borrowed(first)
+        } else {
+            let mut vec = Vec::with_capacity(first.len() + second.len());
+            vec.extend_from_slice(first);
+            vec.extend_from_slice(second);
+            Cow::Owned(vec)
+        }
+    }
+
     /// Make the buffer contiguous.
     ///
     /// The linearization may be required when interacting with external
@@ -587,8 +783,12 @@ impl<T, const CAP: usize> ArrayDequeBase<T, CAP> {
     #[inline]
     pub fn linearize(&mut self) {
         if self.start > 0 {
-            self.arr.rotate_left(self.start);
-            self.end = self.end.wrapping_sub(self.start) & Self::MAX_IDX;
+            if size_of::<T>() > Self::LARGE_SIZE {
+                unsafe { self.rotate_arr_left_cycles(self.start) };
+            } else {
+                self.arr.rotate_left(self.start);
+            }
+            self.end = Self::wrap_sub(self.end, self.start);
             self.start = 0;
         }
     }
@@ -626,646 +826,8320 @@ impl<T, const CAP: usize> ArrayDequeBase<T, CAP> {
         }
     }
 
-    /// Clears the buffer by resetting the indexes.
+    /// Returns a 64-bit FNV-1a hash of the elements in logical order.
+    ///
+    /// Unlike the `Hash` trait with `std`'s `DefaultHasher`, the result is stable across
+    /// program runs, platforms and crate versions, so it can be persisted.
+    /// Integers are hashed in little-endian; other types are as stable as their `Hash` impl.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<u8, 4> = ArrayDeque::new();
+    ///
+    /// assert_eq!(buf.stable_hash(), 0xcbf29ce484222325);
+    ///
+    /// buf.push_last(3).unwrap();
+    /// buf.push_first(2).unwrap();
+    ///
+    /// let mut other: ArrayDeque<u8, 8> = ArrayDeque::new();
+    /// other.push_last(2).unwrap();
+    /// other.push_last(3).unwrap();
+    ///
+    /// assert_eq!(buf.stable_hash(), other.stable_hash());
+    /// ```
+    #[cfg(feature = "stable_hash")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stable_hash")))]
     #[inline]
-    pub fn clear(&mut self) {
-        self.start = 0;
-        self.end = 0;
-        self.full = false;
+    pub fn stable_hash(&self) -> u64
+    where
+        T: Hash,
+    {
+        let mut hasher = crate::stable_hash::Fnv1a::new();
+        let (first, second) = self.as_slices();
+        for v in first.iter().chain(second) {
+            v.hash(&mut hasher);
+        }
+        hasher.finish()
     }
-}
-
-macro_rules! reimpl_common_methods {
-    ($struct_name:ident $(< $($struct_gen:tt),* $(,)? >)?) => {
-        impl<T $(: $($struct_gen +)*)?, const CAP: usize> $struct_name<T, CAP> {
-            #[doc = concat!("
-                Creates an empty ", stringify!($struct_name), ".
-                
-                # Examples
-                
-                ```
-                use array_buf::", stringify!($struct_name), ";
-                
-                let buf: ", stringify!($struct_name), "<usize, 2> = ", stringify!($struct_name), "::new();
-                ```
-            ")]
-            #[inline(always)]
-            pub const fn new() -> Self {
-                Self(ArrayDequeBase::new())
-            }
-
-            #[doc = concat!("
-                Returns the capacity of the array.
-                
-                # Examples
-                
-                ```
-                use array_buf::", stringify!($struct_name), ";
-                
-                let buf: ", stringify!($struct_name), "<usize, 2> = ", stringify!($struct_name), "::new();
-                
-                assert_eq!(buf.capacity(), 2);
-                ```
-            ")]
-            #[inline(always)]
-            pub const fn capacity(&self) -> usize {
-                self.0.capacity()
-            }
 
-            #[doc = concat!("
-                Returns the number of elements in the array.
+    /// Removes the element at the logical `index` and replaces it with the first element.
+    /// Returns the removed element and the new logical index of the moved element,
+    /// or `None` instead of the index if nothing was moved (`index == 0`).
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(1).unwrap();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_last(3).unwrap();
+    ///
+    /// assert_eq!(buf.swap_remove_first_tracked(2), Some((3, Some(1))));
+    /// assert_eq!(buf.as_slices(), (&[2, 1][..], &[][..]));
+    ///
+    /// assert_eq!(buf.swap_remove_first_tracked(0), Some((2, None)));
+    /// assert_eq!(buf.swap_remove_first_tracked(1), None);
+    /// ```
+    #[inline]
+    pub fn swap_remove_first_tracked(&mut self, index: usize) -> Option<(T, Option<usize>)> {
+        if index >= self.len() {
+            return None;
+        }
+        let idx = self.idx(index);
+        let first = unsafe { self.pop_first_unchecked() };
+        if index == 0 {
+            return Some((first, None));
+        }
+        let val = unsafe { self.arr.get_unchecked_mut(idx).assume_init_mut() };
+        Some((mem::replace(val, first), Some(index - 1)))
+    }
 
-                # Examples
+    /// Removes the element at the logical `index` and replaces it with the last element.
+    /// Returns the removed element and the new logical index of the moved element,
+    /// or `None` instead of the index if nothing was moved (`index == len - 1`).
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(1).unwrap();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_first(0).unwrap();
+    ///
+    /// assert_eq!(buf.swap_remove_last_tracked(0), Some((0, Some(0))));
+    /// assert_eq!(buf.as_slices(), (&[2][..], &[1][..]));
+    ///
+    /// assert_eq!(buf.swap_remove_last_tracked(1), Some((1, None)));
+    /// assert_eq!(buf.swap_remove_last_tracked(1), None);
+    /// ```
+    #[inline]
+    pub fn swap_remove_last_tracked(&mut self, index: usize) -> Option<(T, Option<usize>)> {
+        let len = self.len();
+        if index >= len {
+            return None;
+        }
+        let idx = self.idx(index);
+        let last = unsafe { self.pop_last_unchecked() };
+        if index == len - 1 {
+            return Some((last, None));
+        }
+        let val = unsafe { self.arr.get_unchecked_mut(idx).assume_init_mut() };
+        Some((mem::replace(val, last), Some(index)))
+    }
 
-                ```
-                use array_buf::", stringify!($struct_name), ";
+    /// Moves all the elements of `other` (which may have a different capacity) to the back of `self`,
+    /// leaving `other` empty.
+    ///
+    /// Return `Ok` if the append succeeds, or `Err` (moving nothing) if the elements don't fit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 8> = ArrayDeque::new();
+    /// buf.push_last(1).unwrap();
+    ///
+    /// let mut other: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// other.push_last(3).unwrap();
+    /// other.push_first(2).unwrap();
+    ///
+    /// assert!(buf.append_from(&mut other).is_ok());
+    /// assert!(other.is_empty());
+    /// assert_eq!(buf.len(), 3);
+    /// assert_eq!(buf.last(), Some(&3));
+    ///
+    /// let mut small: ArrayDeque<_, 2> = ArrayDeque::new();
+    /// small.push_last(0).unwrap();
+    ///
+    /// assert!(small.append_from(&mut buf).is_err());
+    /// assert_eq!(small.len(), 1);
+    /// assert_eq!(buf.len(), 3);
+    /// ```
+    #[inline]
+    pub fn append_from<const SRC: usize>(
+        &mut self,
+        other: &mut ArrayDequeBase<T, SRC>,
+    ) -> Result<(), &'static str> {
+        if other.len() > self.remaining_capacity() {
+            return Err("not enough capacity");
+        }
+        let (first, second) = other.as_slices();
+        for v in first.iter().chain(second) {
+            unsafe { self.push_last_unchecked(ptr::read(v)) };
+        }
+        // values are moved, so only reset the indexes
+        other.clear();
+        Ok(())
+    }
 
-                let mut buf: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
-                assert_eq!(buf.len(), 0);
+    /// Pushes the elements of `iter` to the back until it's exhausted,
+    /// or returns the first element that doesn't fit.
+    #[inline]
+    pub(crate) fn try_extend<I: IntoIterator<Item = T>>(
+        &mut self,
+        iter: I,
+    ) -> Result<(), CapacityError<T>> {
+        for v in iter {
+            if self.is_full() {
+                return Err(CapacityError(v));
+            }
+            unsafe { self.push_last_unchecked(v) };
+        }
+        Ok(())
+    }
 
-                buf.push_last(1).unwrap();
+    /// Reserves `n` slots at the start of the deque and returns them to be written,
+    /// e.g. a header which is known only after the body is pushed.
+    ///
+    /// Return `Err` if the array doesn't have `n` free slots.
+    ///
+    /// # Safety
+    ///
+    /// All the returned slots must be initialized before the deque is used again
+    /// (including drop), because they are already counted as elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    /// use std::mem::MaybeUninit;
+    ///
+    /// let mut buf: ArrayDeque<u8, 8> = ArrayDeque::new();
+    /// buf.push_last(7).unwrap();
+    /// buf.push_last(8).unwrap();
+    ///
+    /// let header = unsafe { buf.push_front_placeholder(2) }.unwrap();
+    /// header[0] = MaybeUninit::new(0);
+    /// header[1] = MaybeUninit::new(2);
+    ///
+    /// assert_eq!(buf.len(), 4);
+    /// assert_eq!(buf.pop_first(), Some(0));
+    /// assert_eq!(buf.pop_first(), Some(2));
+    /// assert_eq!(buf.pop_first(), Some(7));
+    /// assert_eq!(buf.pop_first(), Some(8));
+    /// ```
+    #[inline]
+    pub unsafe fn push_front_placeholder(
+        &mut self,
+        n: usize,
+    ) -> Result<&mut [MaybeUninit<T>], &'static str> {
+        if n > self.remaining_capacity() {
+            return Err("array is full");
+        }
+        if n == 0 {
+            return Ok(&mut []);
+        }
+        if self.start < n {
+            // move the elements, so the reserved slots don't wrap
+            let shift = n - self.start;
+            self.arr.rotate_right(shift);
+            self.end = Self::wrap_add(self.end, shift);
+            self.start = n;
+        }
+        self.start -= n;
+        self.full = self.start == self.end;
+        Ok(unsafe { self.arr.get_unchecked_mut(self.start..self.start + n) })
+    }
 
-                assert_eq!(buf.len(), 1);
-                ```
+    /// Drops the values (in order) without resetting the indexes.
+    #[inline]
+    pub(crate) fn drop_arr_vals(&mut self) {
+        let (first, second) = self.as_mut_slices();
+        // dropping in order, from the first to the last
+        for v in first.iter_mut().chain(second) {
+            unsafe { ptr::drop_in_place(v) };
+        }
+    }
 
-                ```
-                use array_buf::", stringify!($struct_name), ";
+    /// Moves all the elements out into an owning iterator, leaving the deque empty.
+    ///
+    /// Unlike `into_iter()`, doesn't consume the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(1).unwrap();
+    /// buf.push_first(0).unwrap();
+    ///
+    /// let mut iter = buf.take_all();
+    ///
+    /// assert!(buf.is_empty());
+    /// assert_eq!(iter.next(), Some(0));
+    /// assert_eq!(iter.next(), Some(1));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[inline]
+    pub fn take_all(&mut self) -> IntoIter<T, CAP> {
+        let mut taken = Self::new();
+        taken.overwrite = self.overwrite;
+        IntoIter(mem::replace(self, taken))
+    }
 
-                let mut buf: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
+    /// Binary searches a sorted sequence which was rotated (e.g. by overwriting pushes),
+    /// so its smallest element may be at any logical index.
+    ///
+    /// If the value is found then `Ok` is returned, containing its logical index.
+    /// If it's not found then `Err` is returned, containing the logical index where
+    /// it could be inserted while keeping the rotated sorted order.
+    /// Elements are expected to be unique, otherwise the pivot may be found incorrectly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 8> = ArrayDeque::new();
+    /// for v in [40, 50, 10, 20, 30] {
+    ///     buf.push_last(v).unwrap();
+    /// }
+    ///
+    /// assert_eq!(buf.binary_search_rotated(&40), Ok(0));
+    /// assert_eq!(buf.binary_search_rotated(&10), Ok(2));
+    /// assert_eq!(buf.binary_search_rotated(&30), Ok(4));
+    /// assert_eq!(buf.binary_search_rotated(&25), Err(4));
+    /// assert_eq!(buf.binary_search_rotated(&5), Err(2));
+    /// assert_eq!(buf.binary_search_rotated(&35), Err(5));
+    /// ```
+    #[inline]
+    pub fn binary_search_rotated(&self, x: &T) -> Result<usize, usize>
+    where
+        T: Ord,
+    {
+        let len = self.len();
+        if len == 0 {
+            return Err(0);
+        }
+        let at = |i: usize| unsafe { self.arr.get_unchecked(self.idx(i)).assume_init_ref() };
+
+        // logical index of the smallest element
+        let mut lo = 0;
+        let mut hi = len - 1;
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if at(mid) > at(hi) {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        let pivot = lo;
+
+        // search in sorted order: `k` maps to the logical `(pivot + k) % len`
+        let mut lo = 0;
+        let mut hi = len;
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let mut i = pivot + mid;
+            if i >= len {
+                i -= len;
+            }
+            match at(i).cmp(x) {
+                Ordering::Less => lo = mid + 1,
+                Ordering::Greater => hi = mid,
+                Ordering::Equal => return Ok(i),
+            }
+        }
+        let i = pivot + lo;
+        Err(if i > len { i - len } else { i })
+    }
+
+    /// Provides mut references to the first and the last elements at once.
+    ///
+    /// Returns `None` if there are less than 2 elements, because then the first
+    /// and the last elements are the same one (use `first_mut()` for it).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// assert_eq!(buf.first_last_mut(), None);
+    ///
+    /// buf.push_last(1).unwrap();
+    /// assert_eq!(buf.first_last_mut(), None);
+    ///
+    /// buf.push_first(0).unwrap();
+    /// buf.push_last(2).unwrap();
+    ///
+    /// if let Some((first, last)) = buf.first_last_mut() {
+    ///     *first += 10;
+    ///     *last += 10;
+    /// }
+    /// assert_eq!(buf.as_slices(), (&[10][..], &[1, 12][..]));
+    /// ```
+    #[inline]
+    pub fn first_last_mut(&mut self) -> Option<(&mut T, &mut T)> {
+        let len = self.len();
+        if len < 2 {
+            return None;
+        }
+        let last = self.idx(len - 1);
+        let [first, last] = unsafe { self.arr.get_disjoint_unchecked_mut([self.start, last]) };
+        Some(unsafe { (first.assume_init_mut(), last.assume_init_mut()) })
+    }
+
+    /// Pushes the elements of `iter` to the back until it's full.
+    /// Doesn't take more elements from `iter` than fit.
+    #[inline]
+    pub(crate) fn extend_truncate<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut iter = iter.into_iter();
+        while !self.is_full() {
+            let Some(v) = iter.next() else {
+                break;
+            };
+            unsafe { self.push_last_unchecked(v) };
+        }
+    }
+
+    /// Returns a reference to the first element (from the start) matching the predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_last(4).unwrap();
+    /// buf.push_first(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// assert_eq!(buf.find(|v| v % 2 == 0), Some(&2));
+    /// assert_eq!(buf.find(|v| *v > 2), Some(&3));
+    /// assert_eq!(buf.find(|v| *v > 4), None);
+    /// ```
+    #[inline]
+    pub fn find<F: FnMut(&T) -> bool>(&self, mut pred: F) -> Option<&T> {
+        let (first, second) = self.as_slices();
+        first.iter().chain(second).find(|v| pred(v))
+    }
+
+    /// Returns a mut reference to the first element (from the start) matching the predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_last(4).unwrap();
+    /// buf.push_first(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// *buf.find_mut(|v| *v > 1).unwrap() = 0;
+    /// assert_eq!(buf.as_slices(), (&[1, 0][..], &[3, 4][..]));
+    /// ```
+    #[inline]
+    pub fn find_mut<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> Option<&mut T> {
+        let (first, second) = self.as_mut_slices();
+        first.iter_mut().chain(second).find(|v| pred(v))
+    }
+
+    /// Returns a reference to the last element (searching from the end) matching the predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_last(4).unwrap();
+    /// buf.push_first(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// assert_eq!(buf.rfind(|v| v % 2 == 1), Some(&3));
+    /// assert_eq!(buf.rfind(|v| *v < 3), Some(&2));
+    /// assert_eq!(buf.rfind(|v| *v > 4), None);
+    /// ```
+    #[inline]
+    pub fn rfind<F: FnMut(&T) -> bool>(&self, mut pred: F) -> Option<&T> {
+        let (first, second) = self.as_slices();
+        second
+            .iter()
+            .rev()
+            .chain(first.iter().rev())
+            .find(|v| pred(v))
+    }
+
+    /// Returns a mut reference to the last element (searching from the end) matching the predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_last(4).unwrap();
+    /// buf.push_first(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// *buf.rfind_mut(|v| *v < 4).unwrap() = 0;
+    /// assert_eq!(buf.as_slices(), (&[1, 2][..], &[0, 4][..]));
+    /// ```
+    #[inline]
+    pub fn rfind_mut<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> Option<&mut T> {
+        let (first, second) = self.as_mut_slices();
+        second
+            .iter_mut()
+            .rev()
+            .chain(first.iter_mut().rev())
+            .find(|v| pred(v))
+    }
+
+    /// Sets the overwrite mode. When enabled, `push_last()` (`push_first()`) on a full deque
+    /// drops the first (last) element instead of returning `Err`, so it works as a ring buffer.
+    ///
+    /// The mode is stored in one `bool`, usually absorbed by the padding, but not always
+    /// (e.g. `ArrayDeque<u8, 7>` is 32 bytes instead of 24 on 64-bit targets).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
+    /// buf.push_last(1).unwrap();
+    /// buf.push_last(2).unwrap();
+    /// assert!(buf.push_last(3).is_err());
+    ///
+    /// buf.set_overwrite(true);
+    /// buf.push_last(3).unwrap();
+    /// assert_eq!(buf.as_slices(), (&[2][..], &[3][..]));
+    ///
+    /// buf.push_first(1).unwrap();
+    /// assert_eq!(buf.as_slices(), (&[1, 2][..], &[][..]));
+    /// ```
+    #[inline]
+    pub fn set_overwrite(&mut self, enabled: bool) {
+        self.overwrite = enabled;
+    }
+
+    /// Returns true if the overwrite mode is enabled (see `set_overwrite()`).
+    #[inline(always)]
+    pub fn is_overwrite(&self) -> bool {
+        self.overwrite
+    }
+
+    /// Converts the value and adds it to the start of the deque.
+    ///
+    /// Return `Ok` if the push succeeds, or `Err` distinguishing whether
+    /// the conversion failed or the array is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::{ArrayDeque, PushError};
+    ///
+    /// let mut buf: ArrayDeque<u32, 2> = ArrayDeque::new();
+    /// assert_eq!(buf.try_push_first(1u64), Ok(()));
+    /// assert_eq!(buf.try_push_first(0u64), Ok(()));
+    /// assert!(matches!(buf.try_push_first(-1i64), Err(PushError::Conversion(_))));
+    /// assert_eq!(buf.try_push_first(2u64), Err(PushError::Full));
+    /// assert_eq!(buf.as_slices(), (&[0, 1][..], &[][..]));
+    /// ```
+    #[inline]
+    pub fn try_push_first<U: TryInto<T>>(&mut self, value: U) -> Result<(), PushError<U::Error>> {
+        let element = value.try_into().map_err(PushError::Conversion)?;
+        self.push_first(element).map_err(|_| PushError::Full)
+    }
+
+    /// Converts the value and adds it to the end of the deque.
+    ///
+    /// Return `Ok` if the push succeeds, or `Err` distinguishing whether
+    /// the conversion failed or the array is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::{ArrayDeque, PushError};
+    ///
+    /// let mut buf: ArrayDeque<u32, 2> = ArrayDeque::new();
+    /// assert_eq!(buf.try_push_last(1u64), Ok(()));
+    /// assert!(matches!(buf.try_push_last(u64::MAX), Err(PushError::Conversion(_))));
+    /// assert_eq!(buf.try_push_last(2u64), Ok(()));
+    /// assert_eq!(buf.try_push_last(3u64), Err(PushError::Full));
+    /// assert_eq!(buf.as_slices(), (&[1, 2][..], &[][..]));
+    /// ```
+    #[inline]
+    pub fn try_push_last<U: TryInto<T>>(&mut self, value: U) -> Result<(), PushError<U::Error>> {
+        let element = value.try_into().map_err(PushError::Conversion)?;
+        self.push_last(element).map_err(|_| PushError::Full)
+    }
+
+    /// Calls `f` with mut references to the symmetric pairs of elements: the `i`-th from the start
+    /// and the `i`-th from the end, for `i` in `0..len / 2`. The middle element of an odd length isn't visited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 8> = ArrayDeque::new();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_last(4).unwrap();
+    /// buf.push_first(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// buf.for_each_logical_pair_mut(|a, b| *a += *b);
+    ///
+    /// assert_eq!(buf.as_slices(), (&[5, 5][..], &[3, 4][..]));
+    /// ```
+    #[inline]
+    pub fn for_each_logical_pair_mut<F: FnMut(&mut T, &mut T)>(&mut self, mut f: F) {
+        let len = self.len();
+        for i in 0..len / 2 {
+            let pair = [self.idx(i), self.idx(len - 1 - i)];
+            let [a, b] = unsafe { self.arr.get_disjoint_unchecked_mut(pair) };
+            f(unsafe { a.assume_init_mut() }, unsafe {
+                b.assume_init_mut()
+            });
+        }
+    }
+
+    /// Provides a reference to the element at the logical `index` (`0` is the first element),
+    /// or `None` if out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_first(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// assert_eq!(buf.get(0), Some(&1));
+    /// assert_eq!(buf.get(2), Some(&3));
+    /// assert_eq!(buf.get(3), None);
+    /// ```
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len() {
+            Some(unsafe { self.get_unchecked(index) })
+        } else {
+            None
+        }
+    }
+
+    /// Provides a mut reference to the element at the logical `index` (`0` is the first element),
+    /// or `None` if out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_first(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// *buf.get_mut(1).unwrap() = 0;
+    /// assert_eq!(buf.as_slices(), (&[1, 0][..], &[3][..]));
+    /// assert_eq!(buf.get_mut(3), None);
+    /// ```
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.len() {
+            Some(unsafe { self.get_unchecked_mut(index) })
+        } else {
+            None
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `index` must be less than `len()`.
+    #[inline]
+    pub unsafe fn get_unchecked(&self, index: usize) -> &T {
+        debug_assert!(index < self.len());
+
+        unsafe { self.arr.get_unchecked(self.idx(index)).assume_init_ref() }
+    }
+
+    /// # Safety
+    ///
+    /// `index` must be less than `len()`.
+    #[inline]
+    pub unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut T {
+        debug_assert!(index < self.len());
+
+        let idx = self.idx(index);
+        unsafe { self.arr.get_unchecked_mut(idx).assume_init_mut() }
+    }
+
+    /// Returns a front-to-back iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// assert!(buf.iter().eq(&[1, 2, 3]));
+    /// assert!(buf.iter().rev().eq(&[3, 2, 1]));
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self.as_slices())
+    }
+
+    /// Returns a front-to-back iterator that allows modifying each element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// for v in buf.iter_mut() {
+    ///     *v *= 10;
+    /// }
+    ///
+    /// assert!(buf.iter().eq(&[10, 20, 30]));
+    /// ```
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut::new(self.as_mut_slices())
+    }
+
+    /// Copies the slice to the back with at most two `memcpy`.
+    #[inline]
+    pub(crate) fn extend_from_slice(&mut self, src: &[T]) -> Result<(), &'static str>
+    where
+        T: Copy,
+    {
+        if src.len() > self.remaining_capacity() {
+            return Err("not enough capacity");
+        }
+        if src.is_empty() {
+            return Ok(());
+        }
+        // the part until the end of the array, then the rest from the beginning
+        let first_len = src.len().min(CAP - self.end);
+        unsafe {
+            let dst = self.arr.as_mut_ptr().cast::<T>();
+            ptr::copy_nonoverlapping(src.as_ptr(), dst.add(self.end), first_len);
+            ptr::copy_nonoverlapping(src.as_ptr().add(first_len), dst, src.len() - first_len);
+        }
+        self.end = Self::wrap_add(self.end, src.len());
+        self.full = self.start == self.end;
+        Ok(())
+    }
+
+    /// Compares the logical contents with the slice.
+    #[inline]
+    pub(crate) fn eq_slice<U>(&self, other: &[U]) -> bool
+    where
+        T: PartialEq<U>,
+    {
+        let (first, second) = self.as_slices();
+        first.len() + second.len() == other.len()
+            && *first == other[..first.len()]
+            && *second == other[first.len()..]
+    }
+
+    /// Compares the slice with the logical contents, the mirror of `eq_slice`.
+    #[inline]
+    pub(crate) fn slice_eq<U: PartialEq<T>>(&self, other: &[U]) -> bool {
+        let (first, second) = self.as_slices();
+        first.len() + second.len() == other.len()
+            && other[..first.len()] == *first
+            && other[first.len()..] == *second
+    }
+
+    /// Creates a full deque from the array, the first element is `arr[0]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf = ArrayDeque::from_array([1, 2, 3, 4]);
+    ///
+    /// assert!(buf.is_full());
+    /// assert_eq!(buf.pop_first(), Some(1));
+    /// assert_eq!(buf.pop_last(), Some(4));
+    /// ```
+    #[inline]
+    pub fn from_array(arr: [T; CAP]) -> Self {
+        let mut buf = Self::new();
+        let arr = ManuallyDrop::new(arr);
+        // the values are moved, `arr` is not dropped
+        buf.arr = unsafe { ptr::read((&raw const *arr).cast::<[MaybeUninit<T>; CAP]>()) };
+        buf.full = true;
+        buf
+    }
+
+    /// Returns the elements as an array (in order) if the deque is full,
+    /// otherwise returns the deque back in `Err`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_last(4).unwrap();
+    /// buf.push_first(2).unwrap();
+    ///
+    /// let mut buf = buf.into_array().unwrap_err();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// assert_eq!(buf.into_array().ok(), Some([1, 2, 3, 4]));
+    /// ```
+    #[inline]
+    pub fn into_array(mut self) -> Result<[T; CAP], Self> {
+        if !self.full {
+            return Err(self);
+        }
+        self.linearize();
+        // all the values are initialized, and the array of `MaybeUninit` is not dropped
+        Ok(unsafe { ptr::read((&raw const self.arr).cast::<[T; CAP]>()) })
+    }
+
+    /// Returns `true` if the deque contains an element equal to the given value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_first(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    /// assert!(!buf.as_slices().1.is_empty());
+    ///
+    /// assert!(buf.contains(&1));
+    /// assert!(buf.contains(&3));
+    /// assert!(!buf.contains(&4));
+    /// ```
+    #[inline]
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        let (first, second) = self.as_slices();
+        first.contains(x) || second.contains(x)
+    }
+
+    /// Returns the logical index (from the start) of the first element matching the predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_last(4).unwrap();
+    /// buf.push_first(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// assert_eq!(buf.position(|v| *v > 2), Some(2));
+    /// assert_eq!(buf.position(|v| *v > 4), None);
+    /// ```
+    #[inline]
+    pub fn position<P: FnMut(&T) -> bool>(&self, mut pred: P) -> Option<usize> {
+        let (first, second) = self.as_slices();
+        match first.iter().position(&mut pred) {
+            Some(i) => Some(i),
+            None => second.iter().position(pred).map(|i| first.len() + i),
+        }
+    }
+
+    /// Returns the logical index (from the start) of the last element matching the predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_last(4).unwrap();
+    /// buf.push_first(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// assert_eq!(buf.rposition(|v| *v < 3), Some(1));
+    /// assert_eq!(buf.rposition(|v| *v > 4), None);
+    /// ```
+    #[inline]
+    pub fn rposition<P: FnMut(&T) -> bool>(&self, mut pred: P) -> Option<usize> {
+        let (first, second) = self.as_slices();
+        match second.iter().rposition(&mut pred) {
+            Some(i) => Some(first.len() + i),
+            None => first.iter().rposition(pred),
+        }
+    }
+
+    /// Binary searches the sorted deque for the given element.
+    /// Works directly on the two slices, so there is no need to `linearize()` first.
+    ///
+    /// If the value is found then `Ok` is returned, containing the logical index of the
+    /// matching element (any of them, if there are several matches).
+    /// If it's not found then `Err` is returned, containing the logical index where
+    /// the element could be inserted while maintaining the sorted order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 8> = ArrayDeque::new();
+    /// for v in [5, 8, 13] {
+    ///     buf.push_last(v).unwrap();
+    /// }
+    /// for v in [3, 2, 1, 1] {
+    ///     buf.push_first(v).unwrap();
+    /// }
+    ///
+    /// assert_eq!(buf.binary_search(&3), Ok(3));
+    /// assert_eq!(buf.binary_search(&13), Ok(6));
+    /// assert_eq!(buf.binary_search(&4), Err(4));
+    /// assert_eq!(buf.binary_search(&100), Err(7));
+    /// assert!(matches!(buf.binary_search(&1), Ok(0..=1)));
+    /// ```
+    #[inline]
+    pub fn binary_search(&self, x: &T) -> Result<usize, usize>
+    where
+        T: Ord,
+    {
+        self.binary_search_by(|v| v.cmp(x))
+    }
+
+    /// Binary searches the sorted deque with a comparator function, which returns
+    /// the ordering of the element relative to the target.
+    ///
+    /// See `binary_search()` for the returned value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 8> = ArrayDeque::new();
+    /// for v in [5, 8, 13] {
+    ///     buf.push_last(v).unwrap();
+    /// }
+    /// for v in [3, 2, 1, 1] {
+    ///     buf.push_first(v).unwrap();
+    /// }
+    ///
+    /// assert_eq!(buf.binary_search_by(|v| v.cmp(&8)), Ok(5));
+    /// assert_eq!(buf.binary_search_by(|v| v.cmp(&0)), Err(0));
+    /// ```
+    #[inline]
+    pub fn binary_search_by<F: FnMut(&T) -> Ordering>(&self, mut f: F) -> Result<usize, usize> {
+        let (first, second) = self.as_slices();
+        match second.first().map(&mut f) {
+            Some(Ordering::Equal) => Ok(first.len()),
+            Some(Ordering::Less) => second
+                .binary_search_by(f)
+                .map(|i| first.len() + i)
+                .map_err(|i| first.len() + i),
+            _ => first.binary_search_by(f),
+        }
+    }
+
+    /// Binary searches the deque, sorted by the key extracted with the function.
+    ///
+    /// See `binary_search()` for the returned value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last((3, 'c')).unwrap();
+    /// buf.push_first((2, 'b')).unwrap();
+    /// buf.push_first((1, 'a')).unwrap();
+    ///
+    /// assert_eq!(buf.binary_search_by_key(&3, |&(k, _)| k), Ok(2));
+    /// assert_eq!(buf.binary_search_by_key(&0, |&(k, _)| k), Err(0));
+    /// ```
+    #[inline]
+    pub fn binary_search_by_key<B: Ord, F: FnMut(&T) -> B>(
+        &self,
+        b: &B,
+        mut f: F,
+    ) -> Result<usize, usize> {
+        self.binary_search_by(|v| f(v).cmp(b))
+    }
+
+    /// Retains only the elements matching the predicate, keeping their order.
+    /// The removed elements are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 8> = ArrayDeque::new();
+    /// for v in [4, 5, 6] {
+    ///     buf.push_last(v).unwrap();
+    /// }
+    /// for v in [3, 2, 1] {
+    ///     buf.push_first(v).unwrap();
+    /// }
+    ///
+    /// buf.retain(|v| v % 2 == 0);
+    /// assert_eq!(buf, [2, 4, 6]);
+    /// ```
+    #[inline]
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        self.retain_mut(|v| f(v));
+    }
+
+    /// Retains only the elements matching the predicate, which may also modify them,
+    /// keeping their order. The removed elements are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 8> = ArrayDeque::new();
+    /// for v in [4, 5, 6] {
+    ///     buf.push_last(v).unwrap();
+    /// }
+    /// for v in [3, 2, 1] {
+    ///     buf.push_first(v).unwrap();
+    /// }
+    ///
+    /// buf.retain_mut(|v| {
+    ///     *v *= 10;
+    ///     *v > 30
+    /// });
+    /// assert_eq!(buf, [40, 50, 60]);
+    /// ```
+    #[inline]
+    pub fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, mut f: F) {
+        let len = self.len();
+        // kept elements are swapped towards the front, so the deque stays valid even if `f` panics
+        let mut kept = 0;
+        for i in 0..len {
+            let idx = self.idx(i);
+            if f(unsafe { self.arr.get_unchecked_mut(idx).assume_init_mut() }) {
+                if kept != i {
+                    let kept_idx = self.idx(kept);
+                    self.arr.swap(kept_idx, idx);
+                }
+                kept += 1;
+            }
+        }
+        for _ in kept..len {
+            drop(unsafe { self.pop_last_unchecked() });
+        }
+    }
+
+    /// Removes and returns the element at the logical `index`, or `None` if out of bounds.
+    /// Keeps the order by shifting the elements of the shorter side to fill the gap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 8> = ArrayDeque::new();
+    /// for v in [4, 5, 6] {
+    ///     buf.push_last(v).unwrap();
+    /// }
+    /// for v in [3, 2, 1] {
+    ///     buf.push_first(v).unwrap();
+    /// }
+    ///
+    /// assert_eq!(buf.remove(1), Some(2));
+    /// assert_eq!(buf.remove(3), Some(5));
+    /// assert_eq!(buf.remove(4), None);
+    /// assert_eq!(buf, [1, 3, 4, 6]);
+    /// ```
+    #[inline]
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        let len = self.len();
+        if index >= len {
+            return None;
+        }
+        let idx = self.idx(index);
+        let val = unsafe { self.arr.get_unchecked(idx).assume_init_read() };
+        if index < len - 1 - index {
+            // shift the front part towards the back
+            for i in (0..index).rev() {
+                unsafe { self.copy_logical(i, i + 1) };
+            }
+            self.start = Self::wrap_add(self.start, 1);
+        } else {
+            // shift the back part towards the front
+            for i in index + 1..len {
+                unsafe { self.copy_logical(i, i - 1) };
+            }
+            self.end = Self::wrap_sub(self.end, 1);
+        }
+        self.full = false;
+        Some(val)
+    }
+
+    /// Removes the element at the logical `index` and replaces it with the first element,
+    /// so it doesn't preserve the order, but is O(1).
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// assert_eq!(buf.swap_remove_first(2), Some(3));
+    /// assert_eq!(buf, [2, 1]);
+    /// assert_eq!(buf.swap_remove_first(2), None);
+    /// ```
+    #[inline]
+    pub fn swap_remove_first(&mut self, index: usize) -> Option<T> {
+        self.swap_remove_first_tracked(index).map(|(v, _)| v)
+    }
+
+    /// Removes the element at the logical `index` and replaces it with the last element,
+    /// so it doesn't preserve the order, but is O(1).
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// assert_eq!(buf.swap_remove_last(0), Some(1));
+    /// assert_eq!(buf, [3, 2]);
+    /// assert_eq!(buf.swap_remove_last(2), None);
+    /// ```
+    #[inline]
+    pub fn swap_remove_last(&mut self, index: usize) -> Option<T> {
+        self.swap_remove_last_tracked(index).map(|(v, _)| v)
+    }
+
+    /// Inserts the element at the logical `index`, shifting the elements of the shorter side.
+    ///
+    /// Returns the element back in `Err` if the deque is full (even in the overwrite mode).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than `len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_first(0).unwrap();
+    ///
+    /// buf.insert(1, 1).unwrap();
+    /// buf.insert(3, 3).unwrap();
+    /// assert_eq!(buf, [0, 1, 2, 3]);
+    ///
+    /// assert_eq!(buf.insert(0, 9), Err(9));
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, index: usize, element: T) -> Result<(), T> {
+        let len = self.len();
+        assert!(
+            index <= len,
+            "insertion index (is {index}) should be <= len (is {len})"
+        );
+        if self.full {
+            return Err(element);
+        }
+        if index < len - index {
+            // shift the front part towards the front
+            self.start = Self::wrap_sub(self.start, 1);
+            for i in 0..index {
+                unsafe { self.copy_logical(i + 1, i) };
+            }
+        } else {
+            // shift the back part towards the back
+            for i in (index..len).rev() {
+                unsafe { self.copy_logical(i, i + 1) };
+            }
+            self.end = Self::wrap_add(self.end, 1);
+        }
+        self.full = self.start == self.end;
+        let idx = self.idx(index);
+        unsafe { self.arr.get_unchecked_mut(idx).write(element) };
+        Ok(())
+    }
+
+    /// Swaps the elements at the logical indexes `i` and `j`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// buf.swap(0, 2);
+    /// assert_eq!(buf, [3, 2, 1]);
+    /// ```
+    #[inline]
+    pub fn swap(&mut self, i: usize, j: usize) {
+        let len = self.len();
+        assert!(
+            i < len,
+            "index out of bounds: the len is {len} but the index is {i}"
+        );
+        assert!(
+            j < len,
+            "index out of bounds: the len is {len} but the index is {j}"
+        );
+        let (i, j) = (self.idx(i), self.idx(j));
+        self.arr.swap(i, j);
+    }
+
+    /// Reverses the order of the elements in place, without linearizing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// buf.reverse();
+    /// assert_eq!(buf, [3, 2, 1]);
+    /// ```
+    #[inline]
+    pub fn reverse(&mut self) {
+        self.for_each_logical_pair_mut(mem::swap);
+    }
+
+    /// Rotates the deque `n` places to the left (`n` is taken modulo `len()`),
+    /// so the first `n` elements move to the back.
+    ///
+    /// It's O(1) when the deque is full (only the indexes are moved),
+    /// otherwise it's O(min(n, len - n)).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 8> = ArrayDeque::new();
+    /// for v in 0..5 {
+    ///     buf.push_last(v).unwrap();
+    /// }
+    ///
+    /// buf.rotate_left(2);
+    /// assert_eq!(buf, [2, 3, 4, 0, 1]);
+    /// buf.rotate_left(9);
+    /// assert_eq!(buf, [1, 2, 3, 4, 0]);
+    /// ```
+    #[inline]
+    pub fn rotate_left(&mut self, n: usize) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        let n = n % len;
+        if self.full {
+            self.start = Self::wrap_add(self.start, n);
+            self.end = self.start;
+        } else if n <= len - n {
+            for _ in 0..n {
+                unsafe {
+                    let v = self.pop_first_unchecked();
+                    self.push_last_unchecked(v);
+                }
+            }
+        } else {
+            for _ in 0..len - n {
+                unsafe {
+                    let v = self.pop_last_unchecked();
+                    self.push_first_unchecked(v);
+                }
+            }
+        }
+    }
+
+    /// Rotates the deque `n` places to the right (`n` is taken modulo `len()`),
+    /// so the last `n` elements move to the front.
+    ///
+    /// It's O(1) when the deque is full (only the indexes are moved),
+    /// otherwise it's O(min(n, len - n)).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// for v in 0..4 {
+    ///     buf.push_last(v).unwrap();
+    /// }
+    ///
+    /// buf.rotate_right(1);
+    /// assert_eq!(buf, [3, 0, 1, 2]);
+    /// ```
+    #[inline]
+    pub fn rotate_right(&mut self, n: usize) {
+        let len = self.len();
+        if len != 0 {
+            self.rotate_left(len - n % len);
+        }
+    }
+
+    /// Moves the elements at the logical indexes `at..` into a new deque, leaving `0..at` in `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is greater than `len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// let tail = buf.split_off(1);
+    /// assert_eq!(buf, [1]);
+    /// assert_eq!(tail, [2, 3]);
+    /// ```
+    #[inline]
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let len = self.len();
+        assert!(
+            at <= len,
+            "`at` split index (is {at}) should be <= len (is {len})"
+        );
+        let mut other = Self::new();
+        other.overwrite = self.overwrite;
+        for i in at..len {
+            let idx = self.idx(i);
+            let v = unsafe { self.arr.get_unchecked(idx).assume_init_read() };
+            unsafe { other.push_last_unchecked(v) };
+        }
+        if at < len {
+            self.end = self.idx(at);
+            self.full = false;
+        }
+        other
+    }
+
+    /// Moves all the elements of `other` to the back of `self`, leaving `other` empty.
+    ///
+    /// Return `Ok` if the append succeeds, or `Err` (moving nothing) if the elements don't fit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(1).unwrap();
+    ///
+    /// let mut other: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// other.push_last(3).unwrap();
+    /// other.push_first(2).unwrap();
+    ///
+    /// buf.append(&mut other).unwrap();
+    /// assert!(other.is_empty());
+    /// assert_eq!(buf, [1, 2, 3]);
+    ///
+    /// other.push_last(4).unwrap();
+    /// other.push_last(5).unwrap();
+    /// assert!(buf.append(&mut other).is_err());
+    /// assert_eq!(buf, [1, 2, 3]);
+    /// assert_eq!(other, [4, 5]);
+    /// ```
+    #[inline]
+    pub fn append(&mut self, other: &mut Self) -> Result<(), &'static str> {
+        self.append_from(other)
+    }
+
+    /// Adds an element to the end of the deque. If it's full, the first element
+    /// is removed and returned, regardless of the overwrite mode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
+    /// assert_eq!(buf.push_last_overwrite(1), None);
+    /// assert_eq!(buf.push_last_overwrite(2), None);
+    /// assert_eq!(buf.push_last_overwrite(3), Some(1));
+    /// assert_eq!(buf, [2, 3]);
+    /// ```
+    #[inline]
+    pub fn push_last_overwrite(&mut self, element: T) -> Option<T> {
+        let evicted = if self.full {
+            Some(unsafe { self.pop_first_unchecked() })
+        } else {
+            None
+        };
+        unsafe { self.push_last_unchecked(element) };
+        evicted
+    }
+
+    /// Adds an element to the start of the deque. If it's full, the last element
+    /// is removed and returned, regardless of the overwrite mode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
+    /// assert_eq!(buf.push_first_overwrite(1), None);
+    /// assert_eq!(buf.push_first_overwrite(2), None);
+    /// assert_eq!(buf.push_first_overwrite(3), Some(1));
+    /// assert_eq!(buf, [3, 2]);
+    /// ```
+    #[inline]
+    pub fn push_first_overwrite(&mut self, element: T) -> Option<T> {
+        let evicted = if self.full {
+            Some(unsafe { self.pop_last_unchecked() })
+        } else {
+            None
+        };
+        unsafe { self.push_first_unchecked(element) };
+        evicted
+    }
+
+    /// Makes the elements contiguous (if they aren't yet) and returns them as a mutable slice
+    /// in the logical order. Doesn't move anything if the deque is already contiguous.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// let slice = buf.make_contiguous();
+    /// slice.sort_by(|a, b| b.cmp(a));
+    /// assert_eq!(slice, [3, 2, 1]);
+    /// assert_eq!(buf.as_slices(), (&[3, 2, 1][..], &[][..]));
+    /// ```
+    #[inline]
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        let len = self.len();
+        if self.start + len > CAP {
+            self.linearize();
+        }
+        unsafe {
+            self.arr
+                .get_unchecked_mut(self.start..self.start + len)
+                .assume_init_mut()
+        }
+    }
+
+    /// Alias of `first()`, as in `VecDeque`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// assert_eq!(buf.front(), Some(&1));
+    /// ```
+    #[inline(always)]
+    pub fn front(&self) -> Option<&T> {
+        self.first()
+    }
+
+    /// Alias of `first_mut()`, as in `VecDeque`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// *buf.front_mut().unwrap() = 0;
+    /// assert_eq!(buf, [0, 2]);
+    /// ```
+    #[inline(always)]
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.first_mut()
+    }
+
+    /// Alias of `last()`, as in `VecDeque`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// assert_eq!(buf.back(), Some(&2));
+    /// ```
+    #[inline(always)]
+    pub fn back(&self) -> Option<&T> {
+        self.last()
+    }
+
+    /// Alias of `last_mut()`, as in `VecDeque`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// *buf.back_mut().unwrap() = 3;
+    /// assert_eq!(buf, [1, 3]);
+    /// ```
+    #[inline(always)]
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.last_mut()
+    }
+
+    /// Returns the number of elements which can be pushed until the deque is full (`CAP - len()`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// assert_eq!(buf.remaining_capacity(), 4);
+    ///
+    /// buf.push_last(1).unwrap();
+    /// buf.push_first(0).unwrap();
+    /// assert_eq!(buf.remaining_capacity(), 2);
+    /// ```
+    #[inline(always)]
+    pub fn remaining_capacity(&self) -> usize {
+        CAP - self.len()
+    }
+
+    /// Returns the unused slots as two slices, in the order they are filled by `push_last()`.
+    /// After initializing a prefix of them, call `set_len_back()` to add it to the deque.
+    ///
+    /// The first slice starts right after the last element and is filled first,
+    /// the second one starts at the beginning of the array (it's empty if the spare slots don't wrap).
+    /// The slots can be written in any order, as long as the committed prefix is initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(0).unwrap();
+    /// buf.push_last(1).unwrap();
+    /// buf.push_last(2).unwrap();
+    /// buf.pop_first();
+    ///
+    /// let (first, second) = buf.spare_capacity_mut();
+    /// assert_eq!(first.len() + second.len(), 2);
+    /// first[0].write(3);
+    /// second[0].write(4);
+    /// unsafe { buf.set_len_back(2) };
+    ///
+    /// assert_eq!(buf, [1, 2, 3, 4]);
+    /// ```
+    #[inline]
+    pub fn spare_capacity_mut(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        if self.full {
+            return (&mut [], &mut []);
+        }
+        if self.end >= self.start {
+            let (head, tail) = self.arr.split_at_mut(self.end);
+            (tail, &mut head[..self.start])
+        } else {
+            (&mut self.arr[self.end..self.start], &mut [])
+        }
+    }
+
+    /// Alias of [`spare_capacity_mut()`](Self::spare_capacity_mut).
+    #[inline(always)]
+    pub fn as_uninit_slices_mut(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        self.spare_capacity_mut()
+    }
+
+    /// Adds `added` elements to the back, which were written to the slots
+    /// returned by `spare_capacity_mut()`.
+    ///
+    /// # Safety
+    ///
+    /// `added` must not be greater than `remaining_capacity()`, and the first `added` spare slots
+    /// (in the order of `spare_capacity_mut()`) must be initialized.
+    #[inline]
+    pub unsafe fn set_len_back(&mut self, added: usize) {
+        debug_assert!(added <= self.remaining_capacity());
+
+        if added > 0 {
+            self.end = Self::wrap_add(self.end, added);
+            self.full = self.start == self.end;
+        }
+    }
+
+    /// Returns a raw pointer to the first element, so `len()` elements can be read from it
+    /// (e.g. to pass them through FFI).
+    ///
+    /// The buffer must be contiguous (in the logical order), if it's not, use `linearize()` first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    /// buf.linearize();
+    ///
+    /// let slice = unsafe { core::slice::from_raw_parts(buf.as_ptr(), buf.len()) };
+    /// assert_eq!(slice, [1, 2]);
+    /// ```
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        debug_assert!(self.start + self.len() <= CAP);
+
+        unsafe { self.arr.as_ptr().add(self.start).cast() }
+    }
+
+    /// Returns a raw mut pointer to the first element, so `len()` elements can be accessed through it
+    /// (e.g. to pass them through FFI).
+    ///
+    /// The buffer must be contiguous (in the logical order), if it's not, use `linearize()` first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    /// buf.linearize();
+    ///
+    /// unsafe { *buf.as_mut_ptr().add(1) = 3 };
+    /// assert_eq!(buf, [1, 3]);
+    /// ```
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        debug_assert!(self.start + self.len() <= CAP);
+
+        unsafe { self.arr.as_mut_ptr().add(self.start).cast() }
+    }
+
+    /// Adds an element to the end of the deque, or returns it back in `Err` if the deque is full
+    /// (even in the overwrite mode), like `Vec::push_within_capacity`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 1> = ArrayDeque::new();
+    /// buf.set_overwrite(true);
+    ///
+    /// assert_eq!(buf.push_last_within_capacity(1), Ok(()));
+    /// assert_eq!(buf.push_last_within_capacity(2), Err(2));
+    /// assert_eq!(buf, [1]);
+    /// ```
+    #[inline]
+    pub fn push_last_within_capacity(&mut self, element: T) -> Result<(), T> {
+        if self.full {
+            return Err(element);
+        }
+        unsafe { self.push_last_unchecked(element) };
+        Ok(())
+    }
+
+    /// Adds an element to the start of the deque, or returns it back in `Err` if the deque is full
+    /// (even in the overwrite mode), like `Vec::push_within_capacity`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 1> = ArrayDeque::new();
+    /// buf.set_overwrite(true);
+    ///
+    /// assert_eq!(buf.push_first_within_capacity(1), Ok(()));
+    /// assert_eq!(buf.push_first_within_capacity(2), Err(2));
+    /// assert_eq!(buf, [1]);
+    /// ```
+    #[inline]
+    pub fn push_first_within_capacity(&mut self, element: T) -> Result<(), T> {
+        if self.full {
+            return Err(element);
+        }
+        unsafe { self.push_first_unchecked(element) };
+        Ok(())
+    }
+
+    /// Removes the first element and returns it if the predicate returns `true` for it,
+    /// otherwise leaves the deque untouched and returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(1).unwrap();
+    /// buf.push_last(2).unwrap();
+    ///
+    /// assert_eq!(buf.pop_first_if(|v| *v > 2), None);
+    /// assert_eq!(buf.len(), 2);
+    /// assert_eq!(buf.pop_first_if(|v| *v > 0), Some(1));
+    /// assert_eq!(buf.len(), 1);
+    /// ```
+    #[inline]
+    pub fn pop_first_if<F: FnOnce(&T) -> bool>(&mut self, pred: F) -> Option<T> {
+        if pred(self.first()?) {
+            Some(unsafe { self.pop_first_unchecked() })
+        } else {
+            None
+        }
+    }
+
+    /// Removes the last element and returns it if the predicate returns `true` for it,
+    /// otherwise leaves the deque untouched and returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(1).unwrap();
+    /// buf.push_last(2).unwrap();
+    ///
+    /// assert_eq!(buf.pop_last_if(|v| *v > 2), None);
+    /// assert_eq!(buf.len(), 2);
+    /// assert_eq!(buf.pop_last_if(|v| *v > 0), Some(2));
+    /// assert_eq!(buf.len(), 1);
+    /// ```
+    #[inline]
+    pub fn pop_last_if<F: FnOnce(&T) -> bool>(&mut self, pred: F) -> Option<T> {
+        if pred(self.last()?) {
+            Some(unsafe { self.pop_last_unchecked() })
+        } else {
+            None
+        }
+    }
+
+    /// Moves up to `out.len()` elements from the start of the deque into `out`
+    /// (the old values of `out` are dropped), returning how many were moved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 8> = ArrayDeque::new();
+    /// for v in [3, 4, 5] {
+    ///     buf.push_last(v).unwrap();
+    /// }
+    /// buf.push_first(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// let mut out = [0; 3];
+    /// assert_eq!(buf.pop_first_n(&mut out), 3);
+    /// assert_eq!(out, [1, 2, 3]);
+    /// assert_eq!(buf.pop_first_n(&mut out), 2);
+    /// assert_eq!(out[..2], [4, 5]);
+    /// assert_eq!(buf.pop_first_n(&mut out), 0);
+    /// ```
+    #[inline]
+    pub fn pop_first_n(&mut self, out: &mut [T]) -> usize {
+        let n = out.len().min(self.len());
+        for slot in &mut out[..n] {
+            *slot = unsafe { self.pop_first_unchecked() };
+        }
+        n
+    }
+
+    /// Swaps the first and the last elements, does nothing if there are less than 2 elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// buf.swap_first_last();
+    /// assert_eq!(buf, [3, 2, 1]);
+    /// ```
+    #[inline]
+    pub fn swap_first_last(&mut self) {
+        if let Some((first, last)) = self.first_last_mut() {
+            mem::swap(first, last);
+        }
+    }
+
+    /// Removes the elements of the logical `range`, returning them in an iterator.
+    /// The remaining elements are joined (moving the shorter side) when the iterator is dropped.
+    ///
+    /// Until then only the elements before the range stay in the deque, so if the iterator
+    /// is leaked (e.g. with `mem::forget()`), the elements after the range are leaked too.
+    /// The drained elements which weren't yielded are dropped together with the iterator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end, or the end is greater than `len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 8> = ArrayDeque::new();
+    /// for v in [3, 4, 5] {
+    ///     buf.push_last(v).unwrap();
+    /// }
+    /// for v in [2, 1, 0] {
+    ///     buf.push_first(v).unwrap();
+    /// }
+    ///
+    /// let mut drain = buf.drain(1..4);
+    /// assert_eq!(drain.next(), Some(1));
+    /// assert_eq!(drain.next_back(), Some(3));
+    /// drop(drain);
+    /// assert_eq!(buf, [0, 4, 5]);
+    ///
+    /// assert!(buf.drain(..).eq([0, 4, 5]));
+    /// assert!(buf.is_empty());
+    /// ```
+    #[inline]
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T, CAP> {
+        let len = self.len();
+        let Range { start, end } = self.check_range(range);
+
+        // keep only the head until the `Drain` is dropped, so leaking it doesn't expose moved values
+        if start < len {
+            self.end = self.idx(start);
+            self.full = false;
+        }
+        Drain::new(self, start..end, len - end)
+    }
+
+    /// Logical range of the elements, panics if it's out of bounds.
+    fn check_range<R: RangeBounds<usize>>(&self, range: R) -> Range<usize> {
+        let len = self.len();
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.checked_add(1).expect("range start overflow"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n.checked_add(1).expect("range end overflow"),
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => len,
+        };
+        assert!(
+            start <= end,
+            "range start (is {start}) should be <= end (is {end})"
+        );
+        assert!(
+            end <= len,
+            "range end (is {end}) should be <= len (is {len})"
+        );
+        start..end
+    }
+
+    /// Joins the tail of `tail_len` elements, which is `drained` slots after the current elements,
+    /// by moving the shorter side.
+    ///
+    /// # Safety
+    ///
+    /// The tail slots must be initialized.
+    #[inline]
+    pub(crate) unsafe fn join_tail(&mut self, drained: usize, tail_len: usize) {
+        if tail_len == 0 {
+            return;
+        }
+        let head_len = self.len();
+        if drained > 0 {
+            if head_len <= tail_len {
+                for i in (0..head_len).rev() {
+                    unsafe { self.copy_logical(i, i + drained) };
+                }
+                self.start = self.idx(drained);
+            } else {
+                for i in 0..tail_len {
+                    unsafe { self.copy_logical(head_len + drained + i, head_len + i) };
+                }
+            }
+        }
+        self.end = self.idx(head_len + tail_len);
+        self.full = head_len + tail_len == CAP;
+    }
+
+    /// Returns an iterator which removes and yields the elements matching the predicate
+    /// (which may also modify them), keeping the order of the rest.
+    ///
+    /// If the iterator is dropped before it's exhausted, the elements which weren't checked are kept.
+    /// If it's leaked (e.g. with `mem::forget()`), the elements are leaked and the deque stays empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 8> = ArrayDeque::new();
+    /// for v in [4, 5, 6] {
+    ///     buf.push_last(v).unwrap();
+    /// }
+    /// for v in [3, 2, 1] {
+    ///     buf.push_first(v).unwrap();
+    /// }
+    ///
+    /// assert!(buf.extract_if(|v| *v % 2 == 0).eq([2, 4, 6]));
+    /// assert_eq!(buf, [1, 3, 5]);
+    /// ```
+    #[inline]
+    pub fn extract_if<F: FnMut(&mut T) -> bool>(&mut self, pred: F) -> ExtractIf<'_, T, CAP, F> {
+        ExtractIf::new(self, pred)
+    }
+
+    /// Removes the consecutive repeated elements, keeping the first of each run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 8> = ArrayDeque::new();
+    /// for v in [2, 3, 3] {
+    ///     buf.push_last(v).unwrap();
+    /// }
+    /// for v in [2, 1, 1] {
+    ///     buf.push_first(v).unwrap();
+    /// }
+    ///
+    /// buf.dedup();
+    /// assert_eq!(buf, [1, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    /// Removes the consecutive elements which resolve to the same key, keeping the first of each run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 8> = ArrayDeque::new();
+    /// for v in [2, 3, 3] {
+    ///     buf.push_last(v).unwrap();
+    /// }
+    /// for v in [2, 1, 1] {
+    ///     buf.push_first(v).unwrap();
+    /// }
+    ///
+    /// buf.dedup_by_key(|v| *v / 2);
+    /// assert_eq!(buf, [1, 2]);
+    /// ```
+    #[inline]
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, mut key: F) {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    /// Removes the consecutive elements for which `same_bucket(current, previous)` returns `true`,
+    /// keeping the first of each run. The removed elements are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 8> = ArrayDeque::new();
+    /// for v in [2, 3, 3] {
+    ///     buf.push_last(v).unwrap();
+    /// }
+    /// for v in [2, 1, 1] {
+    ///     buf.push_first(v).unwrap();
+    /// }
+    ///
+    /// buf.dedup_by(|a, b| *a - *b == 1);
+    /// assert_eq!(buf, [1, 1, 3, 3]);
+    /// ```
+    #[inline]
+    pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, mut same_bucket: F) {
+        let len = self.len();
+        // kept elements are swapped towards the front, like in `retain_mut()`
+        let mut kept = 1;
+        for i in 1..len {
+            let pair = [self.idx(i), self.idx(kept - 1)];
+            let [cur, prev] = unsafe { self.arr.get_disjoint_unchecked_mut(pair) };
+            if !same_bucket(unsafe { cur.assume_init_mut() }, unsafe {
+                prev.assume_init_mut()
+            }) {
+                if kept != i {
+                    let kept_idx = self.idx(kept);
+                    self.arr.swap(kept_idx, pair[0]);
+                }
+                kept += 1;
+            }
+        }
+        for _ in kept.min(len)..len {
+            drop(unsafe { self.pop_last_unchecked() });
+        }
+    }
+
+    /// Sorts the elements (stable), see `slice::sort()`.
+    /// Linearizes the deque first (see `make_contiguous()`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 8> = ArrayDeque::new();
+    /// for v in [5, -1, 4] {
+    ///     buf.push_last(v).unwrap();
+    /// }
+    /// for v in [2, -3, 1] {
+    ///     buf.push_first(v).unwrap();
+    /// }
+    ///
+    /// buf.sort();
+    /// assert_eq!(buf, [-3, -1, 1, 2, 4, 5]);
+    /// assert!(buf.is_contiguous());
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.make_contiguous().sort();
+    }
+
+    /// Sorts the elements (stable) with a comparator function, see `slice::sort_by()`.
+    /// Linearizes the deque first (see `make_contiguous()`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 8> = ArrayDeque::new();
+    /// for v in [5, -1, 4] {
+    ///     buf.push_last(v).unwrap();
+    /// }
+    /// for v in [2, -3, 1] {
+    ///     buf.push_first(v).unwrap();
+    /// }
+    ///
+    /// buf.sort_by(|a, b| b.cmp(a));
+    /// assert_eq!(buf, [5, 4, 2, 1, -1, -3]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, compare: F) {
+        self.make_contiguous().sort_by(compare);
+    }
+
+    /// Sorts the elements (stable) by the key extracted with the function, see `slice::sort_by_key()`.
+    /// Linearizes the deque first (see `make_contiguous()`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 8> = ArrayDeque::new();
+    /// for v in [5, -1, 4] {
+    ///     buf.push_last(v).unwrap();
+    /// }
+    /// for v in [2, -3, 1] {
+    ///     buf.push_first(v).unwrap();
+    /// }
+    ///
+    /// buf.sort_by_key(|v: &i32| v.abs());
+    /// assert_eq!(buf, [1, -1, 2, -3, 4, 5]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, f: F) {
+        self.make_contiguous().sort_by_key(f);
+    }
+
+    /// Sorts the elements (unstable, but without allocating), see `slice::sort_unstable()`.
+    /// Linearizes the deque first (see `make_contiguous()`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 8> = ArrayDeque::new();
+    /// for v in [5, -1, 4] {
+    ///     buf.push_last(v).unwrap();
+    /// }
+    /// for v in [2, -3, 1] {
+    ///     buf.push_first(v).unwrap();
+    /// }
+    ///
+    /// buf.sort_unstable();
+    /// assert_eq!(buf, [-3, -1, 1, 2, 4, 5]);
+    /// assert!(buf.is_contiguous());
+    /// ```
+    #[inline]
+    pub fn sort_unstable(&mut self)
+    where
+        T: Ord,
+    {
+        self.make_contiguous().sort_unstable();
+    }
+
+    /// Checks if the elements are sorted, without linearizing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_first(1).unwrap();
+    /// assert!(buf.is_sorted());
+    ///
+    /// buf.push_first(4).unwrap();
+    /// assert!(!buf.is_sorted());
+    /// ```
+    #[inline]
+    pub fn is_sorted(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.is_sorted_by(|a, b| a <= b)
+    }
+
+    /// Checks if the elements are sorted using the comparator function, which returns
+    /// whether the two consecutive elements are in order, see `slice::is_sorted_by()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_last(1).unwrap();
+    /// buf.push_first(3).unwrap();
+    ///
+    /// assert!(buf.is_sorted_by(|a, b| a >= b));
+    /// assert!(!buf.is_sorted_by(|a, b| a < b));
+    /// ```
+    #[inline]
+    pub fn is_sorted_by<F: FnMut(&T, &T) -> bool>(&self, mut compare: F) -> bool {
+        let (first, second) = self.as_slices();
+        let across = match (first.last(), second.first()) {
+            (Some(a), Some(b)) => compare(a, b),
+            _ => true,
+        };
+        across && first.is_sorted_by(&mut compare) && second.is_sorted_by(compare)
+    }
+
+    /// Returns an iterator over the overlapping windows of `size` consecutive elements,
+    /// see `slice::windows()`. Linearizes the deque first (see `make_contiguous()`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(3.0).unwrap();
+    /// buf.push_last(5.0).unwrap();
+    /// buf.push_first(1.0).unwrap();
+    ///
+    /// let averages: Vec<f64> = buf.windows(2).map(|w| (w[0] + w[1]) / 2.0).collect();
+    /// assert_eq!(averages, [2.0, 4.0]);
+    /// ```
+    #[inline]
+    pub fn windows(&mut self, size: usize) -> slice::Windows<'_, T> {
+        self.make_contiguous().windows(size)
+    }
+
+    /// Returns an iterator over the chunks of `size` elements (the last one may be shorter),
+    /// see `slice::chunks()`. Linearizes the deque first (see `make_contiguous()`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 8> = ArrayDeque::new();
+    /// for v in [3, 4, 5] {
+    ///     buf.push_last(v).unwrap();
+    /// }
+    /// for v in [2, 1] {
+    ///     buf.push_first(v).unwrap();
+    /// }
+    ///
+    /// let mut chunks = buf.chunks(2);
+    /// assert_eq!(chunks.next(), Some(&[1, 2][..]));
+    /// assert_eq!(chunks.next(), Some(&[3, 4][..]));
+    /// assert_eq!(chunks.next(), Some(&[5][..]));
+    /// assert_eq!(chunks.next(), None);
+    /// ```
+    #[inline]
+    pub fn chunks(&mut self, size: usize) -> slice::Chunks<'_, T> {
+        self.make_contiguous().chunks(size)
+    }
+
+    /// Returns an iterator over the chunks of exactly `size` elements, the remainder is available
+    /// with `remainder()` of the iterator, see `slice::chunks_exact()`.
+    /// Linearizes the deque first (see `make_contiguous()`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 8> = ArrayDeque::new();
+    /// for v in [3, 4, 5] {
+    ///     buf.push_last(v).unwrap();
+    /// }
+    /// for v in [2, 1] {
+    ///     buf.push_first(v).unwrap();
+    /// }
+    ///
+    /// let mut chunks = buf.chunks_exact(2);
+    /// assert_eq!(chunks.next(), Some(&[1, 2][..]));
+    /// assert_eq!(chunks.next(), Some(&[3, 4][..]));
+    /// assert_eq!(chunks.next(), None);
+    /// assert_eq!(chunks.remainder(), [5]);
+    /// ```
+    #[inline]
+    pub fn chunks_exact(&mut self, size: usize) -> slice::ChunksExact<'_, T> {
+        self.make_contiguous().chunks_exact(size)
+    }
+
+    /// Provides mut references to the elements at two different logical indexes at once,
+    /// or `None` if the indexes are equal or out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// if let Some((a, b)) = buf.get_disjoint_mut(2, 0) {
+    ///     *a += *b;
+    /// }
+    /// assert_eq!(buf, [1, 2, 4]);
+    ///
+    /// assert_eq!(buf.get_disjoint_mut(1, 1), None);
+    /// assert_eq!(buf.get_disjoint_mut(0, 3), None);
+    /// ```
+    #[inline]
+    pub fn get_disjoint_mut(&mut self, i: usize, j: usize) -> Option<(&mut T, &mut T)> {
+        let len = self.len();
+        if i == j || i >= len || j >= len {
+            return None;
+        }
+        let pair = [self.idx(i), self.idx(j)];
+        let [a, b] = unsafe { self.arr.get_disjoint_unchecked_mut(pair) };
+        Some(unsafe { (a.assume_init_mut(), b.assume_init_mut()) })
+    }
+
+    /// Decomposes the deque into its raw parts: the array, `start`, `end` and `full`.
+    ///
+    /// The elements are in the slots `start..end`, wrapping around the end of the array,
+    /// and all the slots if `full`. The overwrite mode is not kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// let (arr, start, end, full) = buf.into_raw_parts();
+    /// assert_eq!((start, end, full), (3, 1, false));
+    /// assert_eq!(unsafe { arr[3].assume_init() }, 1);
+    /// assert_eq!(unsafe { arr[0].assume_init() }, 2);
+    /// ```
+    #[inline]
+    pub fn into_raw_parts(self) -> ([MaybeUninit<T>; CAP], usize, usize, bool) {
+        (self.arr, self.start, self.end, self.full)
+    }
+
+    /// Creates a deque from the raw parts, returned by `into_raw_parts()`.
+    /// The overwrite mode is disabled.
+    ///
+    /// # Safety
+    ///
+    /// - `start < CAP` and `end < CAP`.
+    /// - If `full`, then `start == end` and all the slots are initialized.
+    /// - Otherwise the slots `start..end` (wrapping around the end of the array) are initialized,
+    ///   and `start == end` means empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// let (arr, start, end, full) = buf.into_raw_parts();
+    /// let buf = unsafe { ArrayDeque::from_raw_parts(arr, start, end, full) };
+    /// assert!(buf.iter().eq(&[1, 2]));
+    /// ```
+    #[inline]
+    pub unsafe fn from_raw_parts(
+        arr: [MaybeUninit<T>; CAP],
+        start: usize,
+        end: usize,
+        full: bool,
+    ) -> Self {
+        debug_assert!(start < CAP && end < CAP);
+        debug_assert!(!full || start == end);
+
+        Self {
+            arr,
+            start,
+            end,
+            full,
+            overwrite: false,
+        }
+    }
+
+    /// Returns the physical slot (an index into the inner array) of the logical `index`
+    /// (counting from the first element), wrapping around the end of the array.
+    ///
+    /// It's a low-level helper for the code which accesses the slots directly (e.g. in the array
+    /// from `into_raw_parts()`), it doesn't check `index` against `len()`. `index` is taken modulo `CAP`,
+    /// so the result is always less than `CAP`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// assert_eq!(buf.logical_to_physical(0), 3);
+    /// assert_eq!(buf.logical_to_physical(1), 0);
+    /// assert_eq!(buf.logical_to_physical(2), 1);
+    /// ```
+    #[inline(always)]
+    pub fn logical_to_physical(&self, index: usize) -> usize {
+        // a bit mask if `CAP` is a power of two
+        self.idx(index % CAP)
+    }
+
+    /// Returns an iterator over the logical `range` of the elements, in order.
+    ///
+    /// # Panics
+    ///
+    /// If the start of the range is greater than the end, or the end is greater than `len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_last(4).unwrap();
+    /// buf.push_first(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// assert!(buf.range(1..3).eq(&[2, 3]));
+    /// assert!(buf.range(2..).eq(&[3, 4]));
+    /// assert_eq!(buf.range(..0).len(), 0);
+    /// ```
+    #[inline]
+    pub fn range<R: RangeBounds<usize>>(&self, range: R) -> Iter<'_, T> {
+        let Range { start, end } = self.check_range(range);
+        let (first, second) = self.as_slices();
+        let mid = first.len();
+        Iter::new(if end <= mid {
+            (&first[start..end], &[])
+        } else if start >= mid {
+            (&second[start - mid..end - mid], &[])
+        } else {
+            (&first[start..], &second[..end - mid])
+        })
+    }
+
+    /// Returns a mutable iterator over the logical `range` of the elements, in order.
+    ///
+    /// # Panics
+    ///
+    /// If the start of the range is greater than the end, or the end is greater than `len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_last(4).unwrap();
+    /// buf.push_first(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// for v in buf.range_mut(1..=2) {
+    ///     *v *= 10;
+    /// }
+    /// assert_eq!(buf, [1, 20, 30, 4]);
+    /// ```
+    #[inline]
+    pub fn range_mut<R: RangeBounds<usize>>(&mut self, range: R) -> IterMut<'_, T> {
+        let Range { start, end } = self.check_range(range);
+        let (first, second) = self.as_mut_slices();
+        let mid = first.len();
+        IterMut::new(if end <= mid {
+            (&mut first[start..end], &mut [])
+        } else if start >= mid {
+            (&mut second[start - mid..end - mid], &mut [])
+        } else {
+            (&mut first[start..], &mut second[..end - mid])
+        })
+    }
+
+    /// Clones the elements (in order) into a new `Vec`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// assert_eq!(buf.to_vec(), [1, 2]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        let (first, second) = self.as_slices();
+        let mut vec = Vec::with_capacity(first.len() + second.len());
+        vec.extend_from_slice(first);
+        vec.extend_from_slice(second);
+        vec
+    }
+
+    /// Moves the elements (in order) into a new `Vec`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// assert_eq!(buf.into_vec(), [1, 2]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn into_vec(self) -> Vec<T> {
+        let (first, second) = self.as_slices();
+        let len = first.len() + second.len();
+        let mut vec = Vec::with_capacity(len);
+        // the values are moved, `self` doesn't drop them
+        unsafe {
+            let dst = vec.as_mut_ptr();
+            ptr::copy_nonoverlapping(first.as_ptr(), dst, first.len());
+            ptr::copy_nonoverlapping(second.as_ptr(), dst.add(first.len()), second.len());
+            vec.set_len(len);
+        }
+        vec
+    }
+
+    /// Rotates the deque one place to the left, so the first element becomes the last one.
+    /// No-op if `len() < 2`.
+    ///
+    /// It's O(1): if full, only the indexes are changed, otherwise a single element is moved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// for v in 0..3 {
+    ///     buf.push_last(v).unwrap();
+    /// }
+    ///
+    /// buf.rotate_left_one();
+    /// assert_eq!(buf, [1, 2, 0]);
+    /// ```
+    #[inline]
+    pub fn rotate_left_one(&mut self) {
+        if self.full {
+            self.start = Self::wrap_add(self.start, 1);
+            self.end = self.start;
+        } else if self.len() >= 2 {
+            unsafe {
+                let v = self.pop_first_unchecked();
+                self.push_last_unchecked(v);
+            }
+        }
+    }
+
+    /// Rotates the deque one place to the right, so the last element becomes the first one.
+    /// No-op if `len() < 2`.
+    ///
+    /// It's O(1): if full, only the indexes are changed, otherwise a single element is moved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// for v in 0..3 {
+    ///     buf.push_last(v).unwrap();
+    /// }
+    ///
+    /// buf.rotate_right_one();
+    /// assert_eq!(buf, [2, 0, 1]);
+    /// ```
+    #[inline]
+    pub fn rotate_right_one(&mut self) {
+        if self.full {
+            self.start = Self::wrap_sub(self.start, 1);
+            self.end = self.start;
+        } else if self.len() >= 2 {
+            unsafe {
+                let v = self.pop_last_unchecked();
+                self.push_first_unchecked(v);
+            }
+        }
+    }
+
+    /// Returns the first element and an iterator over the rest (in order), or `None` if empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// assert!(buf.split_first().is_none());
+    ///
+    /// buf.push_last(2).unwrap();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// let (first, rest) = buf.split_first().unwrap();
+    /// assert_eq!(first, &1);
+    /// assert!(rest.eq(&[2, 3]));
+    /// ```
+    #[inline]
+    pub fn split_first(&self) -> Option<(&T, Iter<'_, T>)> {
+        let mut iter = self.iter();
+        let first = iter.next()?;
+        Some((first, iter))
+    }
+
+    /// Returns the last element and an iterator over the rest (in order), or `None` if empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// assert!(buf.split_last().is_none());
+    ///
+    /// buf.push_last(2).unwrap();
+    /// buf.push_last(3).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// let (last, rest) = buf.split_last().unwrap();
+    /// assert_eq!(last, &3);
+    /// assert!(rest.eq(&[1, 2]));
+    /// ```
+    #[inline]
+    pub fn split_last(&self) -> Option<(&T, Iter<'_, T>)> {
+        let mut iter = self.iter();
+        let last = iter.next_back()?;
+        Some((last, iter))
+    }
+
+    /// Clears the buffer by resetting the indexes.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.start = 0;
+        self.end = 0;
+        self.full = false;
+    }
+}
+
+macro_rules! reimpl_common_methods {
+    ($struct_name:ident $(< $($struct_gen:tt),* $(,)? >)?) => {
+        impl<T $(: $($struct_gen +)*)?, const CAP: usize> $struct_name<T, CAP> {
+            #[doc = concat!("
+                Creates an empty ", stringify!($struct_name), ".
+                
+                # Examples
+                
+                ```
+                use array_buf::", stringify!($struct_name), ";
+                
+                let buf: ", stringify!($struct_name), "<usize, 2> = ", stringify!($struct_name), "::new();
+                ```
+            ")]
+            #[inline(always)]
+            pub const fn new() -> Self {
+                Self(ArrayDequeBase::new())
+            }
+
+            #[doc = concat!("
+                Returns the capacity of the array.
+                
+                # Examples
+                
+                ```
+                use array_buf::", stringify!($struct_name), ";
+                
+                let buf: ", stringify!($struct_name), "<usize, 2> = ", stringify!($struct_name), "::new();
+                
+                assert_eq!(buf.capacity(), 2);
+                ```
+            ")]
+            #[inline(always)]
+            pub const fn capacity(&self) -> usize {
+                self.0.capacity()
+            }
+
+            #[doc = concat!("
+                Returns the number of elements in the array.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
+                assert_eq!(buf.len(), 0);
+
+                buf.push_last(1).unwrap();
+
+                assert_eq!(buf.len(), 1);
+                ```
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
                 assert_eq!(buf.len(), 0);
 
-                buf.push_first(-1).unwrap();
+                buf.push_first(-1).unwrap();
+
+                assert_eq!(buf.len(), 1);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn len(&self) -> usize {
+                self.0.len()
+            }
+
+            #[doc = concat!("
+                Returns true if the array contains no elements.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
+                assert!(buf.is_empty());
+
+                buf.push_last(1).unwrap();
+
+                assert!(!buf.is_empty());
+                ```
+            ")]
+            #[inline(always)]
+            pub fn is_empty(&self) -> bool {
+                self.0.is_empty()
+            }
+
+            #[doc = concat!("
+                Returns true if the array is full.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
+                assert!(!buf.is_full());
+
+                buf.push_last(1).unwrap();
+                buf.push_last(2).unwrap();
+
+                assert!(buf.is_full());
+                ```
+            ")]
+            #[inline(always)]
+            pub fn is_full(&self) -> bool {
+                self.0.is_full()
+            }
+
+            /// Order can be compromised once full.
+            #[inline(always)]
+            pub fn is_contiguous_any_order(&self) -> bool {
+                self.0.is_contiguous_any_order()
+            }
+
+            #[inline(always)]
+            pub fn is_contiguous(&self) -> bool {
+                self.0.is_contiguous()
+            }
+
+            /// # Safety
+            ///
+            /// Must not be empty.
+            #[inline(always)]
+            pub unsafe fn pop_first_unchecked(&mut self) -> T {
+                unsafe { self.0.pop_first_unchecked() }
+            }
+
+            /// # Safety
+            ///
+            /// Must not be empty.
+            #[inline(always)]
+            pub unsafe fn pop_last_unchecked(&mut self) -> T {
+                unsafe { self.0.pop_last_unchecked() }
+            }
+
+            #[doc = concat!("
+                Removes the first element and returns it, or `None` if empty.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
+                assert_eq!(buf.pop_first(), None);
+
+                buf.push_last(1).unwrap();
+                buf.push_last(2).unwrap();
+
+                assert_eq!(buf.pop_first(), Some(1));
+                assert_eq!(buf.pop_first(), Some(2));
+                assert_eq!(buf.pop_first(), None);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn pop_first(&mut self) -> Option<T> {
+                self.0.pop_first()
+            }
+
+            #[doc = concat!("
+                Removes the last element and returns it, or `None` if empty.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
+                assert_eq!(buf.pop_last(), None);
+
+                buf.push_last(1).unwrap();
+                buf.push_last(2).unwrap();
+
+                assert_eq!(buf.pop_last(), Some(2));
+                assert_eq!(buf.pop_last(), Some(1));
+                assert_eq!(buf.pop_last(), None);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn pop_last(&mut self) -> Option<T> {
+                self.0.pop_last()
+            }
+
+            /// # Safety
+            ///
+            /// Must not be full.
+            #[inline(always)]
+            pub unsafe fn push_first_unchecked(&mut self, element: T) {
+                unsafe { self.0.push_first_unchecked(element) }
+            }
+
+            /// # Safety
+            ///
+            /// Must not be full.
+            #[inline(always)]
+            pub unsafe fn push_last_unchecked(&mut self, element: T) {
+                unsafe { self.0.push_last_unchecked(element) }
+            }
+
+            #[doc = concat!("
+                Add an element to the start of the deque.
+
+                Return `Ok` if the push succeeds, or `Err` with the element if the array is full.
+                In the overwrite mode (see `set_overwrite()`) drops the last element instead.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
+
+                buf.push_first(-1);
+                buf.push_first(-2);
+
+                let overflow = buf.push_first(-3);
+
+                assert_eq!(overflow.unwrap_err().into_inner(), -3);
+                assert_eq!(buf.first(), Some(&-2));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn push_first(&mut self, element: T) -> Result<(), CapacityError<T>> {
+                self.0.push_first(element)
+            }
+
+            #[doc = concat!("
+                Add an element to the end of the deque.
+
+                Return `Ok` if the push succeeds, or `Err` with the element if the array is full.
+                In the overwrite mode (see `set_overwrite()`) drops the first element instead.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
+
+                buf.push_last(1);
+                buf.push_last(2);
+
+                let overflow = buf.push_last(3);
+
+                assert_eq!(overflow.unwrap_err().into_inner(), 3);
+                assert_eq!(buf.last(), Some(&2));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn push_last(&mut self, element: T) -> Result<(), CapacityError<T>> {
+                self.0.push_last(element)
+            }
+
+            #[doc = concat!("
+                Provides a reference to the first element, or `None` if empty.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
+                assert_eq!(buf.first(), None);
+
+                buf.push_last(1).unwrap();
+                buf.push_last(2).unwrap();
+
+                assert_eq!(buf.first(), Some(&1));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn first(&self) -> Option<&T> {
+                self.0.first()
+            }
+
+            #[doc = concat!("
+                Provides a mut reference to the first element, or `None` if empty.
+
+                # Examples
+
+                ```
+                use array_buf::ArrayDeque;
+
+                let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
+                assert_eq!(buf.first_mut(), None);
+
+                buf.push_last(1).unwrap();
+                buf.push_last(2).unwrap();
+
+                assert_eq!(buf.first_mut(), Some(&mut 1));
+                ```
+            ")]
+            #[inline]
+            pub fn first_mut(&mut self) -> Option<&mut T> {
+                self.0.first_mut()
+            }
+
+            #[doc = concat!("
+                Provides a reference to the last element, or `None` if empty.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
+                assert_eq!(buf.last(), None);
+
+                buf.push_last(1).unwrap();
+                buf.push_last(2).unwrap();
+
+                assert_eq!(buf.last(), Some(&2));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn last(&self) -> Option<&T> {
+                self.0.last()
+            }
+
+            #[doc = concat!("
+                Provides a mut reference to the last element, or `None` if empty.
+
+                # Examples
+
+                ```
+                use array_buf::ArrayDeque;
+
+                let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
+                assert_eq!(buf.last_mut(), None);
+
+                buf.push_last(1).unwrap();
+                buf.push_last(2).unwrap();
+
+                assert_eq!(buf.last_mut(), Some(&mut 2));
+                ```
+            ")]
+            #[inline]
+            pub fn last_mut(&mut self) -> Option<&mut T> {
+                self.0.last_mut()
+            }
+
+            /// Returns a slice which contains the content of the inner buffer.
+            ///
+            /// # Safety
+            ///
+            /// Must be contiguous. If it's not, use `linearize()`.
+            #[inline(always)]
+            pub unsafe fn as_slice(&self) -> &[T] {
+                unsafe { self.0.as_slice() }
+            }
+
+            /// Returns a slice which contains the content of the inner buffer.
+            ///
+            /// # Safety
+            ///
+            /// Must be contiguous. If it's not, use `linearize()`.
+            #[inline(always)]
+            pub unsafe fn as_mut_slice(&mut self) -> &mut [T] {
+                unsafe { self.0.as_mut_slice() }
+            }
+
+            #[doc = concat!("
+                Returns a pair of slices which contain, in order, the contents of the
+                inner buffer.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+
+                assert_eq!(buf.as_slices(), (&[][..], &[][..]));
+
+                buf.push_last(1).unwrap();
+                buf.push_last(2).unwrap();
+
+                assert_eq!(buf.as_slices(), (&[1, 2][..], &[][..]));
+
+                buf.push_first(-1).unwrap();
+
+                assert_eq!(buf.as_slices(), (&[-1][..], &[1, 2][..]));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn as_slices(&self) -> (&[T], &[T]) {
+                self.0.as_slices()
+            }
+
+            #[doc = concat!("
+                Returns a pair of slices which contain, in order, the contents of the
+                inner buffer.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+
+                buf.push_last(1).unwrap();
+                buf.push_last(2).unwrap();
+
+                assert_eq!(buf.as_mut_slices(), (&mut [1, 2][..], &mut[][..]));
+
+                buf.push_first(-1);
+
+                assert_eq!(buf.as_mut_slices(), (&mut[-1][..], &mut[1, 2][..]));
+                ```
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+
+                assert_eq!(buf.as_mut_slices(), (&mut [][..], &mut[][..]));
+
+                buf.push_last(1).unwrap();
+                buf.push_last(2).unwrap();
+
+                assert_eq!(buf.as_mut_slices(), (&mut [1, 2][..], &mut[][..]));
+
+                buf.push_first(-1).unwrap();
+                buf.push_first(-2).unwrap();
+
+                assert_eq!(buf.as_mut_slices(), (&mut[-2, -1][..], &mut[1, 2][..]));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+                self.0.as_mut_slices()
+            }
+
+            #[doc = concat!("
+                Returns the contents as a contiguous slice, borrowed if the buffer
+                is not wrapped, otherwise cloned into an owned `Vec`.
+
+                Unlike `linearize()`, doesn't require `&mut self`.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+                use std::borrow::Cow;
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+
+                buf.push_last(1).unwrap();
+                buf.push_last(2).unwrap();
+
+                assert!(matches!(buf.as_contiguous_cow(), Cow::Borrowed(&[1, 2])));
+
+                buf.push_first(-1).unwrap();
+
+                assert!(matches!(buf.as_contiguous_cow(), Cow::Owned(_)));
+                assert_eq!(buf.as_contiguous_cow(), &[-1, 1, 2][..]);
+                ```
+            ")]
+            #[cfg(feature = "alloc")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+            #[inline(always)]
+            pub fn as_contiguous_cow(&self) -> Cow<'_, [T]>
+            where
+                T: Clone,
+            {
+                self.0.as_contiguous_cow()
+            }
+
+            #[doc = concat!("
+                Returns a 64-bit FNV-1a hash of the elements in logical order.
+
+                Unlike the `Hash` trait with `std`'s `DefaultHasher`, the result is stable across
+                program runs, platforms and crate versions, so it can be persisted.
+                Integers are hashed in little-endian; other types are as stable as their `Hash` impl.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<u8, 4> = ", stringify!($struct_name), "::new();
+
+                assert_eq!(buf.stable_hash(), 0xcbf29ce484222325);
+
+                buf.push_last(3).unwrap();
+                buf.push_first(2).unwrap();
+
+                let mut other: ", stringify!($struct_name), "<u8, 8> = ", stringify!($struct_name), "::new();
+                other.push_last(2).unwrap();
+                other.push_last(3).unwrap();
+
+                assert_eq!(buf.stable_hash(), other.stable_hash());
+                ```
+            ")]
+            #[cfg(feature = "stable_hash")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "stable_hash")))]
+            #[inline(always)]
+            pub fn stable_hash(&self) -> u64
+            where
+                T: Hash,
+            {
+                self.0.stable_hash()
+            }
+
+            #[doc = concat!("
+                Make the buffer contiguous.
+
+                The linearization may be required when interacting with external
+                interfaces requiring contiguous slices.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<isize, 4> = ", stringify!($struct_name), "::new();
+
+                buf.push_last(1).unwrap();
+                buf.push_first(-1).unwrap();
+
+                assert!(!buf.is_contiguous());
+
+                buf.linearize();
+
+                assert!(buf.is_contiguous());
+                ```
+            ")]
+            #[inline(always)]
+            pub fn linearize(&mut self) {
+                self.0.linearize()
+            }
+
+            #[doc = concat!("
+                Make the buffer contiguous.
+
+                The linearization may be required when interacting with external
+                interfaces requiring contiguous slices.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<isize, 2> = ", stringify!($struct_name), "::new();
+
+                buf.push_first(-1).unwrap();
+
+                assert!(!buf.is_contiguous());
+
+                buf.linearize_one();
+
+                assert!(buf.is_contiguous());
+                ```
+            ")]
+            #[inline(always)]
+            pub fn linearize_one(&mut self) {
+                self.0.linearize_one()
+            }
+
+            #[doc = concat!("
+                Removes the element at the logical `index` and replaces it with the first element.
+                Returns the removed element and the new logical index of the moved element,
+                or `None` instead of the index if nothing was moved (`index == 0`).
+
+                Returns `None` if `index` is out of bounds.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(1).unwrap();
+                buf.push_last(2).unwrap();
+                buf.push_last(3).unwrap();
+
+                assert_eq!(buf.swap_remove_first_tracked(2), Some((3, Some(1))));
+                assert_eq!(buf.as_slices(), (&[2, 1][..], &[][..]));
+
+                assert_eq!(buf.swap_remove_first_tracked(0), Some((2, None)));
+                assert_eq!(buf.swap_remove_first_tracked(1), None);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn swap_remove_first_tracked(&mut self, index: usize) -> Option<(T, Option<usize>)> {
+                self.0.swap_remove_first_tracked(index)
+            }
+
+            #[doc = concat!("
+                Removes the element at the logical `index` and replaces it with the last element.
+                Returns the removed element and the new logical index of the moved element,
+                or `None` instead of the index if nothing was moved (`index == len - 1`).
+
+                Returns `None` if `index` is out of bounds.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(1).unwrap();
+                buf.push_last(2).unwrap();
+                buf.push_first(0).unwrap();
+
+                assert_eq!(buf.swap_remove_last_tracked(0), Some((0, Some(0))));
+                assert_eq!(buf.as_slices(), (&[2][..], &[1][..]));
+
+                assert_eq!(buf.swap_remove_last_tracked(1), Some((1, None)));
+                assert_eq!(buf.swap_remove_last_tracked(1), None);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn swap_remove_last_tracked(&mut self, index: usize) -> Option<(T, Option<usize>)> {
+                self.0.swap_remove_last_tracked(index)
+            }
+
+            #[doc = concat!("
+                Moves all the elements of `other` (which may have a different capacity) to the back of `self`,
+                leaving `other` empty.
+
+                Return `Ok` if the append succeeds, or `Err` (moving nothing) if the elements don't fit.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+                buf.push_last(1).unwrap();
+
+                let mut other: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                other.push_last(3).unwrap();
+                other.push_first(2).unwrap();
+
+                assert!(buf.append_from(&mut other).is_ok());
+                assert!(other.is_empty());
+                assert_eq!(buf.len(), 3);
+                assert_eq!(buf.last(), Some(&3));
+
+                let mut small: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
+                small.push_last(0).unwrap();
+
+                assert!(small.append_from(&mut buf).is_err());
+                assert_eq!(small.len(), 1);
+                assert_eq!(buf.len(), 3);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn append_from<const SRC: usize>(&mut self, other: &mut $struct_name<T, SRC>) -> Result<(), &'static str> {
+                self.0.append_from(&mut other.0)
+            }
+
+            #[doc = concat!("
+                Clears the buffer and fills it from `iter`, reusing the storage.
+
+                Return `Ok` if all the elements fit, or `Err` with the first element that doesn't
+                (the buffer then contains the first `CAP` elements).
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+
+                buf.collect_from([1, 2, 3]).unwrap();
+                assert_eq!(buf.as_slices(), (&[1, 2, 3][..], &[][..]));
+
+                buf.collect_from([4, 5]).unwrap();
+                assert_eq!(buf.as_slices(), (&[4, 5][..], &[][..]));
+
+                let overflow = buf.collect_from(0..6);
+                assert_eq!(overflow.unwrap_err().into_inner(), 4);
+                assert_eq!(buf.len(), 4);
+                ```
+            ")]
+            #[inline]
+            pub fn collect_from<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), CapacityError<T>> {
+                self.clear();
+                self.0.try_extend(iter)
+            }
+
+            #[doc = concat!("
+                Reserves `n` slots at the start of the deque and returns them to be written,
+                e.g. a header which is known only after the body is pushed.
+
+                Return `Err` if the array doesn't have `n` free slots.
+
+                # Safety
+
+                All the returned slots must be initialized before the deque is used again
+                (including drop), because they are already counted as elements.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+                use std::mem::MaybeUninit;
+
+                let mut buf: ", stringify!($struct_name), "<u8, 8> = ", stringify!($struct_name), "::new();
+                buf.push_last(7).unwrap();
+                buf.push_last(8).unwrap();
+
+                let header = unsafe { buf.push_front_placeholder(2) }.unwrap();
+                header[0] = MaybeUninit::new(0);
+                header[1] = MaybeUninit::new(2);
+
+                assert_eq!(buf.len(), 4);
+                assert_eq!(buf.pop_first(), Some(0));
+                assert_eq!(buf.pop_first(), Some(2));
+                assert_eq!(buf.pop_first(), Some(7));
+                assert_eq!(buf.pop_first(), Some(8));
+                ```
+            ")]
+            #[inline(always)]
+            pub unsafe fn push_front_placeholder(&mut self, n: usize) -> Result<&mut [MaybeUninit<T>], &'static str> {
+                unsafe { self.0.push_front_placeholder(n) }
+            }
+
+            #[doc = concat!("
+                Moves all the elements out into an owning iterator, leaving the deque empty.
+
+                Unlike `into_iter()`, doesn't consume the deque.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(1).unwrap();
+                buf.push_first(0).unwrap();
+
+                let mut iter = buf.take_all();
+
+                assert!(buf.is_empty());
+                assert_eq!(iter.next(), Some(0));
+                assert_eq!(iter.next(), Some(1));
+                assert_eq!(iter.next(), None);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn take_all(&mut self) -> IntoIter<T, CAP> {
+                self.0.take_all()
+            }
+
+            #[doc = concat!("
+                Binary searches a sorted sequence which was rotated (e.g. by overwriting pushes),
+                so its smallest element may be at any logical index.
+
+                If the value is found then `Ok` is returned, containing its logical index.
+                If it's not found then `Err` is returned, containing the logical index where
+                it could be inserted while keeping the rotated sorted order.
+                Elements are expected to be unique, otherwise the pivot may be found incorrectly.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+                for v in [40, 50, 10, 20, 30] {
+                    buf.push_last(v).unwrap();
+                }
+
+                assert_eq!(buf.binary_search_rotated(&40), Ok(0));
+                assert_eq!(buf.binary_search_rotated(&10), Ok(2));
+                assert_eq!(buf.binary_search_rotated(&30), Ok(4));
+                assert_eq!(buf.binary_search_rotated(&25), Err(4));
+                assert_eq!(buf.binary_search_rotated(&5), Err(2));
+                assert_eq!(buf.binary_search_rotated(&35), Err(5));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn binary_search_rotated(&self, x: &T) -> Result<usize, usize>
+            where
+                T: Ord, {
+                self.0.binary_search_rotated(x)
+            }
+
+            #[doc = concat!("
+                Provides mut references to the first and the last elements at once.
+
+                Returns `None` if there are less than 2 elements, because then the first
+                and the last elements are the same one (use `first_mut()` for it).
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                assert_eq!(buf.first_last_mut(), None);
+
+                buf.push_last(1).unwrap();
+                assert_eq!(buf.first_last_mut(), None);
+
+                buf.push_first(0).unwrap();
+                buf.push_last(2).unwrap();
+
+                if let Some((first, last)) = buf.first_last_mut() {
+                    *first += 10;
+                    *last += 10;
+                }
+                assert_eq!(buf.as_slices(), (&[10][..], &[1, 12][..]));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn first_last_mut(&mut self) -> Option<(&mut T, &mut T)> {
+                self.0.first_last_mut()
+            }
+
+            #[doc = concat!("
+                Returns a reference to the first element (from the start) matching the predicate.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(3).unwrap();
+                buf.push_last(4).unwrap();
+                buf.push_first(2).unwrap();
+                buf.push_first(1).unwrap();
+
+                assert_eq!(buf.find(|v| v % 2 == 0), Some(&2));
+                assert_eq!(buf.find(|v| *v > 2), Some(&3));
+                assert_eq!(buf.find(|v| *v > 4), None);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn find<F: FnMut(&T) -> bool>(&self, pred: F) -> Option<&T> {
+                self.0.find(pred)
+            }
+
+            #[doc = concat!("
+                Returns a mut reference to the first element (from the start) matching the predicate.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(3).unwrap();
+                buf.push_last(4).unwrap();
+                buf.push_first(2).unwrap();
+                buf.push_first(1).unwrap();
+
+                *buf.find_mut(|v| *v > 1).unwrap() = 0;
+                assert_eq!(buf.as_slices(), (&[1, 0][..], &[3, 4][..]));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn find_mut<F: FnMut(&T) -> bool>(&mut self, pred: F) -> Option<&mut T> {
+                self.0.find_mut(pred)
+            }
+
+            #[doc = concat!("
+                Returns a reference to the last element (searching from the end) matching the predicate.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(3).unwrap();
+                buf.push_last(4).unwrap();
+                buf.push_first(2).unwrap();
+                buf.push_first(1).unwrap();
+
+                assert_eq!(buf.rfind(|v| v % 2 == 1), Some(&3));
+                assert_eq!(buf.rfind(|v| *v < 3), Some(&2));
+                assert_eq!(buf.rfind(|v| *v > 4), None);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn rfind<F: FnMut(&T) -> bool>(&self, pred: F) -> Option<&T> {
+                self.0.rfind(pred)
+            }
+
+            #[doc = concat!("
+                Returns a mut reference to the last element (searching from the end) matching the predicate.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(3).unwrap();
+                buf.push_last(4).unwrap();
+                buf.push_first(2).unwrap();
+                buf.push_first(1).unwrap();
+
+                *buf.rfind_mut(|v| *v < 4).unwrap() = 0;
+                assert_eq!(buf.as_slices(), (&[1, 2][..], &[0, 4][..]));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn rfind_mut<F: FnMut(&T) -> bool>(&mut self, pred: F) -> Option<&mut T> {
+                self.0.rfind_mut(pred)
+            }
+
+            #[doc = concat!("
+                Sets the overwrite mode. When enabled, `push_last()` (`push_first()`) on a full deque
+                drops the first (last) element instead of returning `Err`, so it works as a ring buffer.
+
+                The mode is stored in one `bool`, usually absorbed by the padding, but not always
+                (e.g. `ArrayDeque<u8, 7>` is 32 bytes instead of 24 on 64-bit targets).
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
+                buf.push_last(1).unwrap();
+                buf.push_last(2).unwrap();
+                assert!(buf.push_last(3).is_err());
+
+                buf.set_overwrite(true);
+                buf.push_last(3).unwrap();
+                assert_eq!(buf.as_slices(), (&[2][..], &[3][..]));
+
+                buf.push_first(1).unwrap();
+                assert_eq!(buf.as_slices(), (&[1, 2][..], &[][..]));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn set_overwrite(&mut self, enabled: bool) {
+                self.0.set_overwrite(enabled)
+            }
+
+            /// Returns true if the overwrite mode is enabled (see `set_overwrite()`).
+            #[inline(always)]
+            pub fn is_overwrite(&self) -> bool {
+                self.0.is_overwrite()
+            }
+
+            #[doc = concat!("
+                Converts the value and adds it to the start of the deque.
+
+                Return `Ok` if the push succeeds, or `Err` distinguishing whether
+                the conversion failed or the array is full.
+
+                # Examples
+
+                ```
+                use array_buf::{", stringify!($struct_name), ", PushError};
+
+                let mut buf: ", stringify!($struct_name), "<u32, 2> = ", stringify!($struct_name), "::new();
+                assert_eq!(buf.try_push_first(1u64), Ok(()));
+                assert_eq!(buf.try_push_first(0u64), Ok(()));
+                assert!(matches!(buf.try_push_first(-1i64), Err(PushError::Conversion(_))));
+                assert_eq!(buf.try_push_first(2u64), Err(PushError::Full));
+                assert_eq!(buf.as_slices(), (&[0, 1][..], &[][..]));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn try_push_first<U: TryInto<T>>(&mut self, value: U) -> Result<(), PushError<U::Error>> {
+                self.0.try_push_first(value)
+            }
+
+            #[doc = concat!("
+                Converts the value and adds it to the end of the deque.
+
+                Return `Ok` if the push succeeds, or `Err` distinguishing whether
+                the conversion failed or the array is full.
+
+                # Examples
+
+                ```
+                use array_buf::{", stringify!($struct_name), ", PushError};
+
+                let mut buf: ", stringify!($struct_name), "<u32, 2> = ", stringify!($struct_name), "::new();
+                assert_eq!(buf.try_push_last(1u64), Ok(()));
+                assert!(matches!(buf.try_push_last(u64::MAX), Err(PushError::Conversion(_))));
+                assert_eq!(buf.try_push_last(2u64), Ok(()));
+                assert_eq!(buf.try_push_last(3u64), Err(PushError::Full));
+                assert_eq!(buf.as_slices(), (&[1, 2][..], &[][..]));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn try_push_last<U: TryInto<T>>(&mut self, value: U) -> Result<(), PushError<U::Error>> {
+                self.0.try_push_last(value)
+            }
+
+            #[doc = concat!("
+                Calls `f` with mut references to the symmetric pairs of elements: the `i`-th from the start
+                and the `i`-th from the end, for `i` in `0..len / 2`. The middle element of an odd length isn't visited.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+                buf.push_last(3).unwrap();
+                buf.push_last(4).unwrap();
+                buf.push_first(2).unwrap();
+                buf.push_first(1).unwrap();
+
+                buf.for_each_logical_pair_mut(|a, b| *a += *b);
+
+                assert_eq!(buf.as_slices(), (&[5, 5][..], &[3, 4][..]));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn for_each_logical_pair_mut<F: FnMut(&mut T, &mut T)>(&mut self, f: F) {
+                self.0.for_each_logical_pair_mut(f)
+            }
+
+            #[doc = concat!("
+                Provides a reference to the element at the logical `index` (`0` is the first element),
+                or `None` if out of bounds.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(3).unwrap();
+                buf.push_first(2).unwrap();
+                buf.push_first(1).unwrap();
+
+                assert_eq!(buf.get(0), Some(&1));
+                assert_eq!(buf.get(2), Some(&3));
+                assert_eq!(buf.get(3), None);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn get(&self, index: usize) -> Option<&T> {
+                self.0.get(index)
+            }
+
+            #[doc = concat!("
+                Provides a mut reference to the element at the logical `index` (`0` is the first element),
+                or `None` if out of bounds.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(3).unwrap();
+                buf.push_first(2).unwrap();
+                buf.push_first(1).unwrap();
+
+                *buf.get_mut(1).unwrap() = 0;
+                assert_eq!(buf.as_slices(), (&[1, 0][..], &[3][..]));
+                assert_eq!(buf.get_mut(3), None);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+                self.0.get_mut(index)
+            }
+
+            /// # Safety
+            ///
+            /// `index` must be less than `len()`.
+            #[inline(always)]
+            pub unsafe fn get_unchecked(&self, index: usize) -> &T {
+                unsafe { self.0.get_unchecked(index) }
+            }
+
+            /// # Safety
+            ///
+            /// `index` must be less than `len()`.
+            #[inline(always)]
+            pub unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut T {
+                unsafe { self.0.get_unchecked_mut(index) }
+            }
+
+            #[doc = concat!("
+                Returns a front-to-back iterator.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_last(3).unwrap();
+                buf.push_first(1).unwrap();
+
+                assert!(buf.iter().eq(&[1, 2, 3]));
+                assert!(buf.iter().rev().eq(&[3, 2, 1]));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn iter(&self) -> Iter<'_, T> {
+                self.0.iter()
+            }
+
+            #[doc = concat!("
+                Returns a front-to-back iterator that allows modifying each element.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_last(3).unwrap();
+                buf.push_first(1).unwrap();
+
+                for v in buf.iter_mut() {
+                    *v *= 10;
+                }
+
+                assert!(buf.iter().eq(&[10, 20, 30]));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+                self.0.iter_mut()
+            }
+
+            #[doc = concat!("
+                Creates a full deque from the array, the first element is `arr[0]`.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf = ", stringify!($struct_name), "::from_array([1, 2, 3, 4]);
+
+                assert!(buf.is_full());
+                assert_eq!(buf.pop_first(), Some(1));
+                assert_eq!(buf.pop_last(), Some(4));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn from_array(arr: [T; CAP]) -> Self {
+                Self(ArrayDequeBase::from_array(arr))
+            }
+
+            #[doc = concat!("
+                Returns the elements as an array (in order) if the deque is full,
+                otherwise returns the deque back in `Err`.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(3).unwrap();
+                buf.push_last(4).unwrap();
+                buf.push_first(2).unwrap();
+
+                let mut buf = buf.into_array().unwrap_err();
+                buf.push_first(1).unwrap();
+
+                assert_eq!(buf.into_array().ok(), Some([1, 2, 3, 4]));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn into_array(self) -> Result<[T; CAP], Self> {
+                self.into_inner().into_array().map_err(Self)
+            }
+
+            #[doc = concat!("
+                Returns `true` if the deque contains an element equal to the given value.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(3).unwrap();
+                buf.push_first(2).unwrap();
+                buf.push_first(1).unwrap();
+                assert!(!buf.as_slices().1.is_empty());
+
+                assert!(buf.contains(&1));
+                assert!(buf.contains(&3));
+                assert!(!buf.contains(&4));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn contains(&self, x: &T) -> bool
+            where
+                T: PartialEq, {
+                self.0.contains(x)
+            }
+
+            #[doc = concat!("
+                Returns the logical index (from the start) of the first element matching the predicate.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(3).unwrap();
+                buf.push_last(4).unwrap();
+                buf.push_first(2).unwrap();
+                buf.push_first(1).unwrap();
+
+                assert_eq!(buf.position(|v| *v > 2), Some(2));
+                assert_eq!(buf.position(|v| *v > 4), None);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn position<P: FnMut(&T) -> bool>(&self, pred: P) -> Option<usize> {
+                self.0.position(pred)
+            }
+
+            #[doc = concat!("
+                Returns the logical index (from the start) of the last element matching the predicate.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(3).unwrap();
+                buf.push_last(4).unwrap();
+                buf.push_first(2).unwrap();
+                buf.push_first(1).unwrap();
+
+                assert_eq!(buf.rposition(|v| *v < 3), Some(1));
+                assert_eq!(buf.rposition(|v| *v > 4), None);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn rposition<P: FnMut(&T) -> bool>(&self, pred: P) -> Option<usize> {
+                self.0.rposition(pred)
+            }
+
+            #[doc = concat!("
+                Binary searches the sorted deque for the given element.
+                Works directly on the two slices, so there is no need to `linearize()` first.
+
+                If the value is found then `Ok` is returned, containing the logical index of the
+                matching element (any of them, if there are several matches).
+                If it's not found then `Err` is returned, containing the logical index where
+                the element could be inserted while maintaining the sorted order.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+                for v in [5, 8, 13] {
+                    buf.push_last(v).unwrap();
+                }
+                for v in [3, 2, 1, 1] {
+                    buf.push_first(v).unwrap();
+                }
+
+                assert_eq!(buf.binary_search(&3), Ok(3));
+                assert_eq!(buf.binary_search(&13), Ok(6));
+                assert_eq!(buf.binary_search(&4), Err(4));
+                assert_eq!(buf.binary_search(&100), Err(7));
+                assert!(matches!(buf.binary_search(&1), Ok(0..=1)));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn binary_search(&self, x: &T) -> Result<usize, usize>
+            where
+                T: Ord, {
+                self.0.binary_search(x)
+            }
+
+            #[doc = concat!("
+                Binary searches the sorted deque with a comparator function, which returns
+                the ordering of the element relative to the target.
+
+                See `binary_search()` for the returned value.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+                for v in [5, 8, 13] {
+                    buf.push_last(v).unwrap();
+                }
+                for v in [3, 2, 1, 1] {
+                    buf.push_first(v).unwrap();
+                }
+
+                assert_eq!(buf.binary_search_by(|v| v.cmp(&8)), Ok(5));
+                assert_eq!(buf.binary_search_by(|v| v.cmp(&0)), Err(0));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn binary_search_by<F: FnMut(&T) -> Ordering>(&self, f: F) -> Result<usize, usize> {
+                self.0.binary_search_by(f)
+            }
+
+            #[doc = concat!("
+                Binary searches the deque, sorted by the key extracted with the function.
+
+                See `binary_search()` for the returned value.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last((3, 'c')).unwrap();
+                buf.push_first((2, 'b')).unwrap();
+                buf.push_first((1, 'a')).unwrap();
+
+                assert_eq!(buf.binary_search_by_key(&3, |&(k, _)| k), Ok(2));
+                assert_eq!(buf.binary_search_by_key(&0, |&(k, _)| k), Err(0));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn binary_search_by_key<B: Ord, F: FnMut(&T) -> B>(&self, b: &B, f: F) -> Result<usize, usize> {
+                self.0.binary_search_by_key(b, f)
+            }
+
+            #[doc = concat!("
+                Retains only the elements matching the predicate, keeping their order.
+                The removed elements are dropped.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+                for v in [4, 5, 6] {
+                    buf.push_last(v).unwrap();
+                }
+                for v in [3, 2, 1] {
+                    buf.push_first(v).unwrap();
+                }
+
+                buf.retain(|v| v % 2 == 0);
+                assert_eq!(buf, [2, 4, 6]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+                self.0.retain(f)
+            }
+
+            #[doc = concat!("
+                Retains only the elements matching the predicate, which may also modify them,
+                keeping their order. The removed elements are dropped.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+                for v in [4, 5, 6] {
+                    buf.push_last(v).unwrap();
+                }
+                for v in [3, 2, 1] {
+                    buf.push_first(v).unwrap();
+                }
+
+                buf.retain_mut(|v| {
+                    *v *= 10;
+                    *v > 30
+                });
+                assert_eq!(buf, [40, 50, 60]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, f: F) {
+                self.0.retain_mut(f)
+            }
+
+            #[doc = concat!("
+                Removes and returns the element at the logical `index`, or `None` if out of bounds.
+                Keeps the order by shifting the elements of the shorter side to fill the gap.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+                for v in [4, 5, 6] {
+                    buf.push_last(v).unwrap();
+                }
+                for v in [3, 2, 1] {
+                    buf.push_first(v).unwrap();
+                }
+
+                assert_eq!(buf.remove(1), Some(2));
+                assert_eq!(buf.remove(3), Some(5));
+                assert_eq!(buf.remove(4), None);
+                assert_eq!(buf, [1, 3, 4, 6]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn remove(&mut self, index: usize) -> Option<T> {
+                self.0.remove(index)
+            }
+
+            #[doc = concat!("
+                Removes the element at the logical `index` and replaces it with the first element,
+                so it doesn't preserve the order, but is O(1).
+
+                Returns `None` if `index` is out of bounds.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_last(3).unwrap();
+                buf.push_first(1).unwrap();
+
+                assert_eq!(buf.swap_remove_first(2), Some(3));
+                assert_eq!(buf, [2, 1]);
+                assert_eq!(buf.swap_remove_first(2), None);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn swap_remove_first(&mut self, index: usize) -> Option<T> {
+                self.0.swap_remove_first(index)
+            }
+
+            #[doc = concat!("
+                Removes the element at the logical `index` and replaces it with the last element,
+                so it doesn't preserve the order, but is O(1).
+
+                Returns `None` if `index` is out of bounds.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_last(3).unwrap();
+                buf.push_first(1).unwrap();
+
+                assert_eq!(buf.swap_remove_last(0), Some(1));
+                assert_eq!(buf, [3, 2]);
+                assert_eq!(buf.swap_remove_last(2), None);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn swap_remove_last(&mut self, index: usize) -> Option<T> {
+                self.0.swap_remove_last(index)
+            }
+
+            #[doc = concat!("
+                Inserts the element at the logical `index`, shifting the elements of the shorter side.
+
+                Returns the element back in `Err` if the deque is full (even in the overwrite mode).
+
+                # Panics
+
+                Panics if `index` is greater than `len()`.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_first(0).unwrap();
+
+                buf.insert(1, 1).unwrap();
+                buf.insert(3, 3).unwrap();
+                assert_eq!(buf, [0, 1, 2, 3]);
+
+                assert_eq!(buf.insert(0, 9), Err(9));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn insert(&mut self, index: usize, element: T) -> Result<(), T> {
+                self.0.insert(index, element)
+            }
+
+            #[doc = concat!("
+                Swaps the elements at the logical indexes `i` and `j`.
+
+                # Panics
+
+                Panics if either index is out of bounds.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_last(3).unwrap();
+                buf.push_first(1).unwrap();
+
+                buf.swap(0, 2);
+                assert_eq!(buf, [3, 2, 1]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn swap(&mut self, i: usize, j: usize) {
+                self.0.swap(i, j)
+            }
+
+            #[doc = concat!("
+                Reverses the order of the elements in place, without linearizing.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_last(3).unwrap();
+                buf.push_first(1).unwrap();
+
+                buf.reverse();
+                assert_eq!(buf, [3, 2, 1]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn reverse(&mut self) {
+                self.0.reverse()
+            }
+
+            #[doc = concat!("
+                Rotates the deque `n` places to the left (`n` is taken modulo `len()`),
+                so the first `n` elements move to the back.
+
+                It's O(1) when the deque is full (only the indexes are moved),
+                otherwise it's O(min(n, len - n)).
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+                for v in 0..5 {
+                    buf.push_last(v).unwrap();
+                }
+
+                buf.rotate_left(2);
+                assert_eq!(buf, [2, 3, 4, 0, 1]);
+                buf.rotate_left(9);
+                assert_eq!(buf, [1, 2, 3, 4, 0]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn rotate_left(&mut self, n: usize) {
+                self.0.rotate_left(n)
+            }
+
+            #[doc = concat!("
+                Rotates the deque `n` places to the right (`n` is taken modulo `len()`),
+                so the last `n` elements move to the front.
+
+                It's O(1) when the deque is full (only the indexes are moved),
+                otherwise it's O(min(n, len - n)).
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                for v in 0..4 {
+                    buf.push_last(v).unwrap();
+                }
+
+                buf.rotate_right(1);
+                assert_eq!(buf, [3, 0, 1, 2]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn rotate_right(&mut self, n: usize) {
+                self.0.rotate_right(n)
+            }
+
+            #[doc = concat!("
+                Moves the elements at the logical indexes `at..` into a new deque, leaving `0..at` in `self`.
+
+                # Panics
+
+                Panics if `at` is greater than `len()`.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_last(3).unwrap();
+                buf.push_first(1).unwrap();
+
+                let tail = buf.split_off(1);
+                assert_eq!(buf, [1]);
+                assert_eq!(tail, [2, 3]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn split_off(&mut self, at: usize) -> Self {
+                Self(self.0.split_off(at))
+            }
+
+            #[doc = concat!("
+                Moves all the elements of `other` to the back of `self`, leaving `other` empty.
+
+                Return `Ok` if the append succeeds, or `Err` (moving nothing) if the elements don't fit.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(1).unwrap();
+
+                let mut other: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                other.push_last(3).unwrap();
+                other.push_first(2).unwrap();
+
+                buf.append(&mut other).unwrap();
+                assert!(other.is_empty());
+                assert_eq!(buf, [1, 2, 3]);
+
+                other.push_last(4).unwrap();
+                other.push_last(5).unwrap();
+                assert!(buf.append(&mut other).is_err());
+                assert_eq!(buf, [1, 2, 3]);
+                assert_eq!(other, [4, 5]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn append(&mut self, other: &mut Self) -> Result<(), &'static str> {
+                self.0.append(&mut other.0)
+            }
+
+            #[doc = concat!("
+                Adds an element to the end of the deque. If it's full, the first element
+                is removed and returned, regardless of the overwrite mode.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
+                assert_eq!(buf.push_last_overwrite(1), None);
+                assert_eq!(buf.push_last_overwrite(2), None);
+                assert_eq!(buf.push_last_overwrite(3), Some(1));
+                assert_eq!(buf, [2, 3]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn push_last_overwrite(&mut self, element: T) -> Option<T> {
+                self.0.push_last_overwrite(element)
+            }
+
+            #[doc = concat!("
+                Adds an element to the start of the deque. If it's full, the last element
+                is removed and returned, regardless of the overwrite mode.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
+                assert_eq!(buf.push_first_overwrite(1), None);
+                assert_eq!(buf.push_first_overwrite(2), None);
+                assert_eq!(buf.push_first_overwrite(3), Some(1));
+                assert_eq!(buf, [3, 2]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn push_first_overwrite(&mut self, element: T) -> Option<T> {
+                self.0.push_first_overwrite(element)
+            }
+
+            #[doc = concat!("
+                Makes the elements contiguous (if they aren't yet) and returns them as a mutable slice
+                in the logical order. Doesn't move anything if the deque is already contiguous.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_last(3).unwrap();
+                buf.push_first(1).unwrap();
+
+                let slice = buf.make_contiguous();
+                slice.sort_by(|a, b| b.cmp(a));
+                assert_eq!(slice, [3, 2, 1]);
+                assert_eq!(buf.as_slices(), (&[3, 2, 1][..], &[][..]));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn make_contiguous(&mut self) -> &mut [T] {
+                self.0.make_contiguous()
+            }
+
+            #[doc = concat!("
+                Alias of `first()`, as in `VecDeque`.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_first(1).unwrap();
+
+                assert_eq!(buf.front(), Some(&1));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn front(&self) -> Option<&T> {
+                self.0.front()
+            }
+
+            #[doc = concat!("
+                Alias of `first_mut()`, as in `VecDeque`.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_first(1).unwrap();
+
+                *buf.front_mut().unwrap() = 0;
+                assert_eq!(buf, [0, 2]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn front_mut(&mut self) -> Option<&mut T> {
+                self.0.front_mut()
+            }
+
+            #[doc = concat!("
+                Alias of `last()`, as in `VecDeque`.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_first(1).unwrap();
+
+                assert_eq!(buf.back(), Some(&2));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn back(&self) -> Option<&T> {
+                self.0.back()
+            }
+
+            #[doc = concat!("
+                Alias of `last_mut()`, as in `VecDeque`.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_first(1).unwrap();
+
+                *buf.back_mut().unwrap() = 3;
+                assert_eq!(buf, [1, 3]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn back_mut(&mut self) -> Option<&mut T> {
+                self.0.back_mut()
+            }
+
+            #[doc = concat!("
+                Returns the number of elements which can be pushed until the deque is full (`CAP - len()`).
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                assert_eq!(buf.remaining_capacity(), 4);
+
+                buf.push_last(1).unwrap();
+                buf.push_first(0).unwrap();
+                assert_eq!(buf.remaining_capacity(), 2);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn remaining_capacity(&self) -> usize {
+                self.0.remaining_capacity()
+            }
+
+            #[doc = concat!("
+                Returns the unused slots as two slices, in the order they are filled by `push_last()`.
+                After initializing a prefix of them, call `set_len_back()` to add it to the deque.
+
+                The first slice starts right after the last element and is filled first,
+                the second one starts at the beginning of the array (it's empty if the spare slots don't wrap).
+                The slots can be written in any order, as long as the committed prefix is initialized.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(0).unwrap();
+                buf.push_last(1).unwrap();
+                buf.push_last(2).unwrap();
+                buf.pop_first();
+
+                let (first, second) = buf.spare_capacity_mut();
+                assert_eq!(first.len() + second.len(), 2);
+                first[0].write(3);
+                second[0].write(4);
+                unsafe { buf.set_len_back(2) };
+
+                assert_eq!(buf, [1, 2, 3, 4]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn spare_capacity_mut(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+                self.0.spare_capacity_mut()
+            }
+
+            /// Alias of [`spare_capacity_mut()`](Self::spare_capacity_mut).
+            #[inline(always)]
+            pub fn as_uninit_slices_mut(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+                self.0.as_uninit_slices_mut()
+            }
+
+            /// Adds `added` elements to the back, which were written to the slots
+            /// returned by `spare_capacity_mut()`.
+            ///
+            /// # Safety
+            ///
+            /// `added` must not be greater than `remaining_capacity()`, and the first `added` spare slots
+            /// (in the order of `spare_capacity_mut()`) must be initialized.
+            #[inline(always)]
+            pub unsafe fn set_len_back(&mut self, added: usize) {
+                unsafe { self.0.set_len_back(added) }
+            }
+
+            #[doc = concat!("
+                Returns a raw pointer to the first element, so `len()` elements can be read from it
+                (e.g. to pass them through FFI).
+
+                The buffer must be contiguous (in the logical order), if it's not, use `linearize()` first.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_first(1).unwrap();
+                buf.linearize();
+
+                let slice = unsafe { core::slice::from_raw_parts(buf.as_ptr(), buf.len()) };
+                assert_eq!(slice, [1, 2]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn as_ptr(&self) -> *const T {
+                self.0.as_ptr()
+            }
+
+            #[doc = concat!("
+                Returns a raw mut pointer to the first element, so `len()` elements can be accessed through it
+                (e.g. to pass them through FFI).
+
+                The buffer must be contiguous (in the logical order), if it's not, use `linearize()` first.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_first(1).unwrap();
+                buf.linearize();
+
+                unsafe { *buf.as_mut_ptr().add(1) = 3 };
+                assert_eq!(buf, [1, 3]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn as_mut_ptr(&mut self) -> *mut T {
+                self.0.as_mut_ptr()
+            }
+
+            #[doc = concat!("
+                Adds an element to the end of the deque, or returns it back in `Err` if the deque is full
+                (even in the overwrite mode), like `Vec::push_within_capacity`.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 1> = ", stringify!($struct_name), "::new();
+                buf.set_overwrite(true);
+
+                assert_eq!(buf.push_last_within_capacity(1), Ok(()));
+                assert_eq!(buf.push_last_within_capacity(2), Err(2));
+                assert_eq!(buf, [1]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn push_last_within_capacity(&mut self, element: T) -> Result<(), T> {
+                self.0.push_last_within_capacity(element)
+            }
+
+            #[doc = concat!("
+                Adds an element to the start of the deque, or returns it back in `Err` if the deque is full
+                (even in the overwrite mode), like `Vec::push_within_capacity`.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 1> = ", stringify!($struct_name), "::new();
+                buf.set_overwrite(true);
+
+                assert_eq!(buf.push_first_within_capacity(1), Ok(()));
+                assert_eq!(buf.push_first_within_capacity(2), Err(2));
+                assert_eq!(buf, [1]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn push_first_within_capacity(&mut self, element: T) -> Result<(), T> {
+                self.0.push_first_within_capacity(element)
+            }
+
+            #[doc = concat!("
+                Clears the deque, then fills all the slots with the values returned by `f`,
+                so the deque becomes full.
+
+                If `f` panics, the already created elements stay in the deque.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(7).unwrap();
+
+                let mut n = 0;
+                buf.fill_with(|| {
+                    n += 1;
+                    n
+                });
+                assert_eq!(buf, [1, 2, 3, 4]);
+                ```
+            ")]
+            #[inline]
+            pub fn fill_with<F: FnMut() -> T>(&mut self, mut f: F) {
+                self.clear();
+                // pushing one by one, so the created elements are dropped by the deque if `f` panics
+                for _ in 0..CAP {
+                    unsafe { self.0.push_last_unchecked(f()) };
+                }
+            }
+
+            #[doc = concat!("
+                Removes the first element and returns it if the predicate returns `true` for it,
+                otherwise leaves the deque untouched and returns `None`.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(1).unwrap();
+                buf.push_last(2).unwrap();
+
+                assert_eq!(buf.pop_first_if(|v| *v > 2), None);
+                assert_eq!(buf.len(), 2);
+                assert_eq!(buf.pop_first_if(|v| *v > 0), Some(1));
+                assert_eq!(buf.len(), 1);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn pop_first_if<F: FnOnce(&T) -> bool>(&mut self, pred: F) -> Option<T> {
+                self.0.pop_first_if(pred)
+            }
+
+            #[doc = concat!("
+                Removes the last element and returns it if the predicate returns `true` for it,
+                otherwise leaves the deque untouched and returns `None`.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(1).unwrap();
+                buf.push_last(2).unwrap();
+
+                assert_eq!(buf.pop_last_if(|v| *v > 2), None);
+                assert_eq!(buf.len(), 2);
+                assert_eq!(buf.pop_last_if(|v| *v > 0), Some(2));
+                assert_eq!(buf.len(), 1);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn pop_last_if<F: FnOnce(&T) -> bool>(&mut self, pred: F) -> Option<T> {
+                self.0.pop_last_if(pred)
+            }
+
+            #[doc = concat!("
+                Moves up to `out.len()` elements from the start of the deque into `out`
+                (the old values of `out` are dropped), returning how many were moved.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+                for v in [3, 4, 5] {
+                    buf.push_last(v).unwrap();
+                }
+                buf.push_first(2).unwrap();
+                buf.push_first(1).unwrap();
+
+                let mut out = [0; 3];
+                assert_eq!(buf.pop_first_n(&mut out), 3);
+                assert_eq!(out, [1, 2, 3]);
+                assert_eq!(buf.pop_first_n(&mut out), 2);
+                assert_eq!(out[..2], [4, 5]);
+                assert_eq!(buf.pop_first_n(&mut out), 0);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn pop_first_n(&mut self, out: &mut [T]) -> usize {
+                self.0.pop_first_n(out)
+            }
+
+            #[doc = concat!("
+                Swaps the first and the last elements, does nothing if there are less than 2 elements.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_last(3).unwrap();
+                buf.push_first(1).unwrap();
+
+                buf.swap_first_last();
+                assert_eq!(buf, [3, 2, 1]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn swap_first_last(&mut self) {
+                self.0.swap_first_last()
+            }
+
+            #[doc = concat!("
+                Removes the elements of the logical `range`, returning them in an iterator.
+                The remaining elements are joined (moving the shorter side) when the iterator is dropped.
+
+                Until then only the elements before the range stay in the deque, so if the iterator
+                is leaked (e.g. with `mem::forget()`), the elements after the range are leaked too.
+                The drained elements which weren't yielded are dropped together with the iterator.
+
+                # Panics
+
+                Panics if the start of the range is greater than the end, or the end is greater than `len()`.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+                for v in [3, 4, 5] {
+                    buf.push_last(v).unwrap();
+                }
+                for v in [2, 1, 0] {
+                    buf.push_first(v).unwrap();
+                }
+
+                let mut drain = buf.drain(1..4);
+                assert_eq!(drain.next(), Some(1));
+                assert_eq!(drain.next_back(), Some(3));
+                drop(drain);
+                assert_eq!(buf, [0, 4, 5]);
+
+                assert!(buf.drain(..).eq([0, 4, 5]));
+                assert!(buf.is_empty());
+                ```
+            ")]
+            #[inline(always)]
+            pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T, CAP> {
+                self.0.drain(range)
+            }
+
+            #[doc = concat!("
+                Returns an iterator which removes and yields the elements matching the predicate
+                (which may also modify them), keeping the order of the rest.
+
+                If the iterator is dropped before it's exhausted, the elements which weren't checked are kept.
+                If it's leaked (e.g. with `mem::forget()`), the elements are leaked and the deque stays empty.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+                for v in [4, 5, 6] {
+                    buf.push_last(v).unwrap();
+                }
+                for v in [3, 2, 1] {
+                    buf.push_first(v).unwrap();
+                }
+
+                assert!(buf.extract_if(|v| *v % 2 == 0).eq([2, 4, 6]));
+                assert_eq!(buf, [1, 3, 5]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn extract_if<F: FnMut(&mut T) -> bool>(&mut self, pred: F) -> ExtractIf<'_, T, CAP, F> {
+                self.0.extract_if(pred)
+            }
+
+            #[doc = concat!("
+                Removes the consecutive repeated elements, keeping the first of each run.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+                for v in [2, 3, 3] {
+                    buf.push_last(v).unwrap();
+                }
+                for v in [2, 1, 1] {
+                    buf.push_first(v).unwrap();
+                }
+
+                buf.dedup();
+                assert_eq!(buf, [1, 2, 3]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn dedup(&mut self)
+            where
+                T: PartialEq, {
+                self.0.dedup()
+            }
+
+            #[doc = concat!("
+                Removes the consecutive elements which resolve to the same key, keeping the first of each run.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+                for v in [2, 3, 3] {
+                    buf.push_last(v).unwrap();
+                }
+                for v in [2, 1, 1] {
+                    buf.push_first(v).unwrap();
+                }
+
+                buf.dedup_by_key(|v| *v / 2);
+                assert_eq!(buf, [1, 2]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, key: F) {
+                self.0.dedup_by_key(key)
+            }
+
+            #[doc = concat!("
+                Removes the consecutive elements for which `same_bucket(current, previous)` returns `true`,
+                keeping the first of each run. The removed elements are dropped.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+                for v in [2, 3, 3] {
+                    buf.push_last(v).unwrap();
+                }
+                for v in [2, 1, 1] {
+                    buf.push_first(v).unwrap();
+                }
+
+                buf.dedup_by(|a, b| *a - *b == 1);
+                assert_eq!(buf, [1, 1, 3, 3]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, same_bucket: F) {
+                self.0.dedup_by(same_bucket)
+            }
+
+            #[doc = concat!("
+                Sorts the elements (stable), see `slice::sort()`.
+                Linearizes the deque first (see `make_contiguous()`).
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+                for v in [5, -1, 4] {
+                    buf.push_last(v).unwrap();
+                }
+                for v in [2, -3, 1] {
+                    buf.push_first(v).unwrap();
+                }
+
+                buf.sort();
+                assert_eq!(buf, [-3, -1, 1, 2, 4, 5]);
+                assert!(buf.is_contiguous());
+                ```
+            ")]
+            #[cfg(feature = "alloc")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+            #[inline(always)]
+            pub fn sort(&mut self)
+            where
+                T: Ord, {
+                self.0.sort()
+            }
+
+            #[doc = concat!("
+                Sorts the elements (stable) with a comparator function, see `slice::sort_by()`.
+                Linearizes the deque first (see `make_contiguous()`).
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+                for v in [5, -1, 4] {
+                    buf.push_last(v).unwrap();
+                }
+                for v in [2, -3, 1] {
+                    buf.push_first(v).unwrap();
+                }
+
+                buf.sort_by(|a, b| b.cmp(a));
+                assert_eq!(buf, [5, 4, 2, 1, -1, -3]);
+                ```
+            ")]
+            #[cfg(feature = "alloc")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+            #[inline(always)]
+            pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, compare: F) {
+                self.0.sort_by(compare)
+            }
+
+            #[doc = concat!("
+                Sorts the elements (stable) by the key extracted with the function, see `slice::sort_by_key()`.
+                Linearizes the deque first (see `make_contiguous()`).
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+                for v in [5, -1, 4] {
+                    buf.push_last(v).unwrap();
+                }
+                for v in [2, -3, 1] {
+                    buf.push_first(v).unwrap();
+                }
+
+                buf.sort_by_key(|v: &i32| v.abs());
+                assert_eq!(buf, [1, -1, 2, -3, 4, 5]);
+                ```
+            ")]
+            #[cfg(feature = "alloc")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+            #[inline(always)]
+            pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, f: F) {
+                self.0.sort_by_key(f)
+            }
+
+            #[doc = concat!("
+                Sorts the elements (unstable, but without allocating), see `slice::sort_unstable()`.
+                Linearizes the deque first (see `make_contiguous()`).
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+                for v in [5, -1, 4] {
+                    buf.push_last(v).unwrap();
+                }
+                for v in [2, -3, 1] {
+                    buf.push_first(v).unwrap();
+                }
+
+                buf.sort_unstable();
+                assert_eq!(buf, [-3, -1, 1, 2, 4, 5]);
+                assert!(buf.is_contiguous());
+                ```
+            ")]
+            #[inline(always)]
+            pub fn sort_unstable(&mut self)
+            where
+                T: Ord, {
+                self.0.sort_unstable()
+            }
+
+            #[doc = concat!("
+                Checks if the elements are sorted, without linearizing.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_last(3).unwrap();
+                buf.push_first(1).unwrap();
+                assert!(buf.is_sorted());
+
+                buf.push_first(4).unwrap();
+                assert!(!buf.is_sorted());
+                ```
+            ")]
+            #[inline(always)]
+            pub fn is_sorted(&self) -> bool
+            where
+                T: PartialOrd, {
+                self.0.is_sorted()
+            }
+
+            #[doc = concat!("
+                Checks if the elements are sorted using the comparator function, which returns
+                whether the two consecutive elements are in order, see `slice::is_sorted_by()`.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_last(1).unwrap();
+                buf.push_first(3).unwrap();
+
+                assert!(buf.is_sorted_by(|a, b| a >= b));
+                assert!(!buf.is_sorted_by(|a, b| a < b));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn is_sorted_by<F: FnMut(&T, &T) -> bool>(&self, compare: F) -> bool {
+                self.0.is_sorted_by(compare)
+            }
+
+            #[doc = concat!("
+                Returns an iterator over the overlapping windows of `size` consecutive elements,
+                see `slice::windows()`. Linearizes the deque first (see `make_contiguous()`).
+
+                # Panics
+
+                Panics if `size` is 0.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(3.0).unwrap();
+                buf.push_last(5.0).unwrap();
+                buf.push_first(1.0).unwrap();
+
+                let averages: Vec<f64> = buf.windows(2).map(|w| (w[0] + w[1]) / 2.0).collect();
+                assert_eq!(averages, [2.0, 4.0]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn windows(&mut self, size: usize) -> slice::Windows<'_, T> {
+                self.0.windows(size)
+            }
+
+            #[doc = concat!("
+                Returns an iterator over the chunks of `size` elements (the last one may be shorter),
+                see `slice::chunks()`. Linearizes the deque first (see `make_contiguous()`).
+
+                # Panics
+
+                Panics if `size` is 0.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+                for v in [3, 4, 5] {
+                    buf.push_last(v).unwrap();
+                }
+                for v in [2, 1] {
+                    buf.push_first(v).unwrap();
+                }
+
+                let mut chunks = buf.chunks(2);
+                assert_eq!(chunks.next(), Some(&[1, 2][..]));
+                assert_eq!(chunks.next(), Some(&[3, 4][..]));
+                assert_eq!(chunks.next(), Some(&[5][..]));
+                assert_eq!(chunks.next(), None);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn chunks(&mut self, size: usize) -> slice::Chunks<'_, T> {
+                self.0.chunks(size)
+            }
+
+            #[doc = concat!("
+                Returns an iterator over the chunks of exactly `size` elements, the remainder is available
+                with `remainder()` of the iterator, see `slice::chunks_exact()`.
+                Linearizes the deque first (see `make_contiguous()`).
+
+                # Panics
+
+                Panics if `size` is 0.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+                for v in [3, 4, 5] {
+                    buf.push_last(v).unwrap();
+                }
+                for v in [2, 1] {
+                    buf.push_first(v).unwrap();
+                }
+
+                let mut chunks = buf.chunks_exact(2);
+                assert_eq!(chunks.next(), Some(&[1, 2][..]));
+                assert_eq!(chunks.next(), Some(&[3, 4][..]));
+                assert_eq!(chunks.next(), None);
+                assert_eq!(chunks.remainder(), [5]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn chunks_exact(&mut self, size: usize) -> slice::ChunksExact<'_, T> {
+                self.0.chunks_exact(size)
+            }
+
+            #[doc = concat!("
+                Provides mut references to the elements at two different logical indexes at once,
+                or `None` if the indexes are equal or out of bounds.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_last(3).unwrap();
+                buf.push_first(1).unwrap();
+
+                if let Some((a, b)) = buf.get_disjoint_mut(2, 0) {
+                    *a += *b;
+                }
+                assert_eq!(buf, [1, 2, 4]);
+
+                assert_eq!(buf.get_disjoint_mut(1, 1), None);
+                assert_eq!(buf.get_disjoint_mut(0, 3), None);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn get_disjoint_mut(&mut self, i: usize, j: usize) -> Option<(&mut T, &mut T)> {
+                self.0.get_disjoint_mut(i, j)
+            }
+
+            #[doc = concat!("
+                Decomposes the deque into its raw parts: the array, `start`, `end` and `full`.
+
+                The elements are in the slots `start..end`, wrapping around the end of the array,
+                and all the slots if `full`. The overwrite mode is not kept.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_first(1).unwrap();
+
+                let (arr, start, end, full) = buf.into_raw_parts();
+                assert_eq!((start, end, full), (3, 1, false));
+                assert_eq!(unsafe { arr[3].assume_init() }, 1);
+                assert_eq!(unsafe { arr[0].assume_init() }, 2);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn into_raw_parts(self) -> ([MaybeUninit<T>; CAP], usize, usize, bool) {
+                self.into_inner().into_raw_parts()
+            }
+
+            #[doc = concat!("
+                Creates a deque from the raw parts, returned by `into_raw_parts()`.
+                The overwrite mode is disabled.
+
+                # Safety
+
+                - `start < CAP` and `end < CAP`.
+                - If `full`, then `start == end` and all the slots are initialized.
+                - Otherwise the slots `start..end` (wrapping around the end of the array) are initialized,
+                  and `start == end` means empty.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_first(1).unwrap();
+
+                let (arr, start, end, full) = buf.into_raw_parts();
+                let buf = unsafe { ", stringify!($struct_name), "::from_raw_parts(arr, start, end, full) };
+                assert!(buf.iter().eq(&[1, 2]));
+                ```
+            ")]
+            #[inline(always)]
+            pub unsafe fn from_raw_parts(arr: [MaybeUninit<T>; CAP], start: usize, end: usize, full: bool) -> Self {
+                Self(unsafe { ArrayDequeBase::from_raw_parts(arr, start, end, full) })
+            }
+
+            #[doc = concat!("
+                Consumes the deque, and returns a deque of the results of `f`, in the same order.
+
+                If `f` panics, the rest of the elements and the already mapped ones are dropped.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_first(1).unwrap();
+
+                let buf = buf.map(|v| v * 10);
+                assert!(buf.iter().eq(&[10, 20]));
+                ```
+            ")]
+            #[inline]
+            pub fn map<U $(: $($struct_gen +)*)?, F: FnMut(T) -> U>(self, mut f: F) -> $struct_name<U, CAP> {
+                let mut out = $struct_name::new();
+                for v in self {
+                    // the same number of elements fits
+                    unsafe { out.push_last_unchecked(f(v)) };
+                }
+                out
+            }
+
+            #[doc = concat!("
+                Returns the physical slot (an index into the inner array) of the logical `index`
+                (counting from the first element), wrapping around the end of the array.
+
+                It's a low-level helper for the code which accesses the slots directly (e.g. in the array
+                from `into_raw_parts()`), it doesn't check `index` against `len()`. `index` is taken modulo `CAP`,
+                so the result is always less than `CAP`.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_first(1).unwrap();
+
+                assert_eq!(buf.logical_to_physical(0), 3);
+                assert_eq!(buf.logical_to_physical(1), 0);
+                assert_eq!(buf.logical_to_physical(2), 1);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn logical_to_physical(&self, index: usize) -> usize {
+                self.0.logical_to_physical(index)
+            }
+
+            #[doc = concat!("
+                Returns an iterator over the logical `range` of the elements, in order.
+
+                # Panics
+
+                If the start of the range is greater than the end, or the end is greater than `len()`.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(3).unwrap();
+                buf.push_last(4).unwrap();
+                buf.push_first(2).unwrap();
+                buf.push_first(1).unwrap();
+
+                assert!(buf.range(1..3).eq(&[2, 3]));
+                assert!(buf.range(2..).eq(&[3, 4]));
+                assert_eq!(buf.range(..0).len(), 0);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn range<R: RangeBounds<usize>>(&self, range: R) -> Iter<'_, T> {
+                self.0.range(range)
+            }
+
+            #[doc = concat!("
+                Returns a mutable iterator over the logical `range` of the elements, in order.
+
+                # Panics
+
+                If the start of the range is greater than the end, or the end is greater than `len()`.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(3).unwrap();
+                buf.push_last(4).unwrap();
+                buf.push_first(2).unwrap();
+                buf.push_first(1).unwrap();
+
+                for v in buf.range_mut(1..=2) {
+                    *v *= 10;
+                }
+                assert_eq!(buf, [1, 20, 30, 4]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn range_mut<R: RangeBounds<usize>>(&mut self, range: R) -> IterMut<'_, T> {
+                self.0.range_mut(range)
+            }
+
+            #[doc = concat!("
+                Clones the elements (in order) into a new `Vec`.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_first(1).unwrap();
+
+                assert_eq!(buf.to_vec(), [1, 2]);
+                ```
+            ")]
+            #[cfg(feature = "alloc")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+            #[inline(always)]
+            pub fn to_vec(&self) -> Vec<T>
+            where
+                T: Clone, {
+                self.0.to_vec()
+            }
+
+            #[doc = concat!("
+                Moves the elements (in order) into a new `Vec`.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                buf.push_last(2).unwrap();
+                buf.push_first(1).unwrap();
+
+                assert_eq!(buf.into_vec(), [1, 2]);
+                ```
+            ")]
+            #[cfg(feature = "alloc")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+            #[inline(always)]
+            pub fn into_vec(self) -> Vec<T> {
+                self.into_inner().into_vec()
+            }
+
+            #[doc = concat!("
+                Rotates the deque one place to the left, so the first element becomes the last one.
+                No-op if `len() < 2`.
+
+                It's O(1): if full, only the indexes are changed, otherwise a single element is moved.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                for v in 0..3 {
+                    buf.push_last(v).unwrap();
+                }
+
+                buf.rotate_left_one();
+                assert_eq!(buf, [1, 2, 0]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn rotate_left_one(&mut self) {
+                self.0.rotate_left_one()
+            }
+
+            #[doc = concat!("
+                Rotates the deque one place to the right, so the last element becomes the first one.
+                No-op if `len() < 2`.
+
+                It's O(1): if full, only the indexes are changed, otherwise a single element is moved.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                for v in 0..3 {
+                    buf.push_last(v).unwrap();
+                }
+
+                buf.rotate_right_one();
+                assert_eq!(buf, [2, 0, 1]);
+                ```
+            ")]
+            #[inline(always)]
+            pub fn rotate_right_one(&mut self) {
+                self.0.rotate_right_one()
+            }
+
+            #[doc = concat!("
+                Returns the first element and an iterator over the rest (in order), or `None` if empty.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                assert!(buf.split_first().is_none());
+
+                buf.push_last(2).unwrap();
+                buf.push_last(3).unwrap();
+                buf.push_first(1).unwrap();
+
+                let (first, rest) = buf.split_first().unwrap();
+                assert_eq!(first, &1);
+                assert!(rest.eq(&[2, 3]));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn split_first(&self) -> Option<(&T, Iter<'_, T>)> {
+                self.0.split_first()
+            }
+
+            #[doc = concat!("
+                Returns the last element and an iterator over the rest (in order), or `None` if empty.
+
+                # Examples
+
+                ```
+                use array_buf::", stringify!($struct_name), ";
+
+                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+                assert!(buf.split_last().is_none());
+
+                buf.push_last(2).unwrap();
+                buf.push_last(3).unwrap();
+                buf.push_first(1).unwrap();
+
+                let (last, rest) = buf.split_last().unwrap();
+                assert_eq!(last, &3);
+                assert!(rest.eq(&[1, 2]));
+                ```
+            ")]
+            #[inline(always)]
+            pub fn split_last(&self) -> Option<(&T, Iter<'_, T>)> {
+                self.0.split_last()
+            }
+        }
+
+        #[doc = concat!("
+            Accesses an element by the logical index (`0` is the first element).
+
+            # Panics
+
+            Panics if `index >= len()`.
+
+            # Examples
+
+            ```
+            use array_buf::", stringify!($struct_name), ";
+
+            let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+            buf.push_last(2).unwrap();
+            buf.push_first(1).unwrap();
+
+            assert_eq!(buf[0], 1);
+            assert_eq!(buf[1], 2);
+            ```
+        ")]
+        impl<T $(: $($struct_gen +)*)?, const CAP: usize> Index<usize> for $struct_name<T, CAP> {
+            type Output = T;
+
+            #[inline(always)]
+            fn index(&self, index: usize) -> &T {
+                &self.0[index]
+            }
+        }
+
+        #[doc = concat!("
+            Mutably accesses an element by the logical index (`0` is the first element).
+
+            # Panics
+
+            Panics if `index >= len()`.
+
+            # Examples
+
+            ```
+            use array_buf::", stringify!($struct_name), ";
+
+            let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+            buf.push_last(2).unwrap();
+            buf.push_first(1).unwrap();
+
+            buf[1] = 3;
+            assert_eq!(buf.last(), Some(&3));
+            ```
+        ")]
+        impl<T $(: $($struct_gen +)*)?, const CAP: usize> IndexMut<usize> for $struct_name<T, CAP> {
+            #[inline(always)]
+            fn index_mut(&mut self, index: usize) -> &mut T {
+                &mut self.0[index]
+            }
+        }
+
+        impl<'a, T $(: $($struct_gen +)*)?, const CAP: usize> IntoIterator for &'a $struct_name<T, CAP> {
+            type Item = &'a T;
+            type IntoIter = Iter<'a, T>;
+
+            #[inline(always)]
+            fn into_iter(self) -> Iter<'a, T> {
+                self.iter()
+            }
+        }
+
+        impl<'a, T $(: $($struct_gen +)*)?, const CAP: usize> IntoIterator for &'a mut $struct_name<T, CAP> {
+            type Item = &'a mut T;
+            type IntoIter = IterMut<'a, T>;
+
+            #[inline(always)]
+            fn into_iter(self) -> IterMut<'a, T> {
+                self.iter_mut()
+            }
+        }
+
+        #[doc = concat!("
+            Takes the first `CAP` elements, the rest are ignored (not taken from the iterator).
+
+            # Examples
+
+            ```
+            use array_buf::", stringify!($struct_name), ";
+
+            let buf: ", stringify!($struct_name), "<_, 8> = (0..100).collect();
+
+            assert!(buf.iter().copied().eq(0..8));
+            ```
+        ")]
+        impl<T $(: $($struct_gen +)*)?, const CAP: usize> FromIterator<T> for $struct_name<T, CAP> {
+            #[inline]
+            fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+                let mut buf = Self::new();
+                buf.0.extend_truncate(iter);
+                buf
+            }
+        }
+
+        #[doc = concat!("
+            Pushes the elements to the back until it's full, the rest are ignored (not taken from the iterator).
+
+            # Examples
+
+            ```
+            use array_buf::", stringify!($struct_name), ";
+
+            let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+            buf.push_last(1).unwrap();
+            buf.push_last(2).unwrap();
+
+            buf.extend([3, 4, 5, 6]);
+
+            assert!(buf.iter().eq(&[1, 2, 3, 4]));
+            ```
+        ")]
+        impl<T $(: $($struct_gen +)*)?, const CAP: usize> Extend<T> for $struct_name<T, CAP> {
+            #[inline(always)]
+            fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+                self.0.extend_truncate(iter);
+            }
+        }
+
+        #[doc = concat!("
+            Moves the elements (in order) into a `VecDeque`.
+
+            # Examples
+
+            ```
+            use array_buf::", stringify!($struct_name), ";
+            use std::collections::VecDeque;
+
+            let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+            buf.push_last(2).unwrap();
+            buf.push_first(1).unwrap();
+
+            assert_eq!(VecDeque::from(buf), [1, 2]);
+            ```
+        ")]
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        impl<T $(: $($struct_gen +)*)?, const CAP: usize> From<$struct_name<T, CAP>> for VecDeque<T> {
+            #[inline]
+            fn from(buf: $struct_name<T, CAP>) -> Self {
+                let mut vec = VecDeque::with_capacity(buf.len());
+                vec.extend(buf);
+                vec
+            }
+        }
+
+        #[doc = concat!("
+            Moves the elements (in order) from a `VecDeque`, or returns it back if it's longer than `CAP`.
+
+            # Examples
+
+            ```
+            use array_buf::", stringify!($struct_name), ";
+            use std::collections::VecDeque;
+
+            let vec = VecDeque::from([1, 2, 3]);
+            let buf = ", stringify!($struct_name), "::<_, 4>::try_from(vec).unwrap();
+            assert!(buf.iter().eq(&[1, 2, 3]));
+
+            let vec = VecDeque::from([1, 2, 3]);
+            assert_eq!(", stringify!($struct_name), "::<_, 2>::try_from(vec).unwrap_err(), [1, 2, 3]);
+            ```
+        ")]
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        impl<T $(: $($struct_gen +)*)?, const CAP: usize> TryFrom<VecDeque<T>> for $struct_name<T, CAP> {
+            type Error = VecDeque<T>;
+
+            #[inline]
+            fn try_from(vec: VecDeque<T>) -> Result<Self, Self::Error> {
+                if vec.len() > CAP {
+                    return Err(vec);
+                }
+                Ok(vec.into_iter().collect())
+            }
+        }
+
+        #[doc = concat!("
+            Compares the elements in order, regardless of the internal layout.
+
+            # Examples
+
+            ```
+            use array_buf::", stringify!($struct_name), ";
+
+            let mut a: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+            a.push_last(1).unwrap();
+            a.push_last(2).unwrap();
+
+            let mut b: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+            b.push_first(2).unwrap();
+            b.push_first(1).unwrap();
+
+            assert_eq!(a, b);
+            ```
+        ")]
+        impl<T: $($($struct_gen +)*)? PartialEq, const CAP: usize> PartialEq for $struct_name<T, CAP> {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.len() == other.len() && self.iter().eq(other.iter())
+            }
+        }
+
+        impl<T: $($($struct_gen +)*)? Eq, const CAP: usize> Eq for $struct_name<T, CAP> {}
+
+        /// Hashes the length and the elements in order, consistent with `PartialEq`.
+        impl<T: $($($struct_gen +)*)? Hash, const CAP: usize> Hash for $struct_name<T, CAP> {
+            #[inline]
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                state.write_usize(self.len());
+                for v in self.iter() {
+                    v.hash(state);
+                }
+            }
+        }
+
+        impl<T: $($($struct_gen +)*)? PartialOrd, const CAP: usize> PartialOrd for $struct_name<T, CAP> {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                self.iter().partial_cmp(other.iter())
+            }
+        }
+
+        impl<T: $($($struct_gen +)*)? Ord, const CAP: usize> Ord for $struct_name<T, CAP> {
+            #[inline]
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.iter().cmp(other.iter())
+            }
+        }
+
+        #[doc = concat!("
+            Compares the logical contents with a slice, accounting for wrapping.
+
+            # Examples
+
+            ```
+            use array_buf::", stringify!($struct_name), ";
+
+            let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+            buf.push_last(3).unwrap();
+            buf.push_first(2).unwrap();
+            buf.push_first(1).unwrap();
+
+            assert_eq!(buf, [1, 2, 3]);
+            assert_eq!(buf, &[1, 2, 3][..]);
+            assert_eq!([1, 2, 3], buf);
+            assert_ne!(buf, [1, 2]);
+            ```
+        ")]
+        impl<T: $($($struct_gen +)*)? PartialEq<U>, U, const CAP: usize> PartialEq<[U]> for $struct_name<T, CAP> {
+            #[inline]
+            fn eq(&self, other: &[U]) -> bool {
+                self.0.eq_slice(other)
+            }
+        }
+
+        impl<T: $($($struct_gen +)*)? PartialEq<U>, U, const CAP: usize> PartialEq<&[U]> for $struct_name<T, CAP> {
+            #[inline]
+            fn eq(&self, other: &&[U]) -> bool {
+                self.0.eq_slice(other)
+            }
+        }
+
+        impl<T: $($($struct_gen +)*)? PartialEq<U>, U, const CAP: usize> PartialEq<&mut [U]> for $struct_name<T, CAP> {
+            #[inline]
+            fn eq(&self, other: &&mut [U]) -> bool {
+                self.0.eq_slice(other)
+            }
+        }
+
+        impl<T: $($($struct_gen +)*)? PartialEq<U>, U, const CAP: usize, const N: usize> PartialEq<[U; N]> for $struct_name<T, CAP> {
+            #[inline]
+            fn eq(&self, other: &[U; N]) -> bool {
+                self.0.eq_slice(other)
+            }
+        }
+
+        impl<T: $($($struct_gen +)*)? PartialEq<U>, U, const CAP: usize, const N: usize> PartialEq<&[U; N]> for $struct_name<T, CAP> {
+            #[inline]
+            fn eq(&self, other: &&[U; N]) -> bool {
+                self.0.eq_slice(*other)
+            }
+        }
+
+        impl<T $(: $($struct_gen +)*)?, U: PartialEq<T>, const CAP: usize> PartialEq<$struct_name<T, CAP>> for [U] {
+            #[inline]
+            fn eq(&self, other: &$struct_name<T, CAP>) -> bool {
+                other.0.slice_eq(self)
+            }
+        }
+
+        impl<T $(: $($struct_gen +)*)?, U: PartialEq<T>, const CAP: usize> PartialEq<$struct_name<T, CAP>> for &[U] {
+            #[inline]
+            fn eq(&self, other: &$struct_name<T, CAP>) -> bool {
+                other.0.slice_eq(self)
+            }
+        }
+
+        impl<T $(: $($struct_gen +)*)?, U: PartialEq<T>, const CAP: usize, const N: usize> PartialEq<$struct_name<T, CAP>> for [U; N] {
+            #[inline]
+            fn eq(&self, other: &$struct_name<T, CAP>) -> bool {
+                other.0.slice_eq(self)
+            }
+        }
+
+        #[doc = concat!("
+            Appends the UTF-8 bytes of the string to the back, or returns `fmt::Error`
+            (writing nothing) if they don't fit.
+
+            # Examples
+
+            ```
+            use array_buf::", stringify!($struct_name), ";
+            use core::fmt::Write;
+
+            let mut buf: ", stringify!($struct_name), "<u8, 8> = ", stringify!($struct_name), "::new();
+            write!(buf, \"{}-{}\", 12, 34).unwrap();
+            assert_eq!(buf, *b\"12-34\");
+
+            assert!(write!(buf, \"long\").is_err());
+            assert_eq!(buf.len(), 5);
+            ```
+        ")]
+        impl<const CAP: usize> fmt::Write for $struct_name<u8, CAP> {
+            #[inline]
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.0.extend_from_slice(s.as_bytes()).map_err(|_| fmt::Error)
+            }
+        }
+
+        #[doc = concat!("
+            Appends the chars of the string to the back, or returns `fmt::Error`
+            (writing nothing) if they don't fit.
+
+            # Examples
+
+            ```
+            use array_buf::", stringify!($struct_name), ";
+            use core::fmt::Write;
+
+            let mut buf: ", stringify!($struct_name), "<char, 4> = ", stringify!($struct_name), "::new();
+            write!(buf, \"ñ{}\", 2).unwrap();
+            assert_eq!(buf, ['ñ', '2']);
+            ```
+        ")]
+        impl<const CAP: usize> fmt::Write for $struct_name<char, CAP> {
+            #[inline]
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                if s.chars().count() > self.remaining_capacity() {
+                    return Err(fmt::Error);
+                }
+                for c in s.chars() {
+                    unsafe { self.0.push_last_unchecked(c) };
+                }
+                Ok(())
+            }
+
+            #[inline]
+            fn write_char(&mut self, c: char) -> fmt::Result {
+                self.push_last_within_capacity(c).map_err(|_| fmt::Error)
+            }
+        }
+    };
+}
+
+/// A fixed capacity deque for plain data (`Copy`, no `Drop`). Capacity must not be zero.
+///
+/// A power of two capacity is the fastest: indexes wrap with a bit mask,
+/// otherwise with a compare and subtract.
+///
+/// Can be stored directly on the stack.
+///
+/// The "default" usage of this as a queue is to use `push_last` to add to
+/// the queue, and `pop_first` to consume from the queue.
+#[derive(Copy, Clone, Debug, Default)]
+#[repr(transparent)]
+pub struct ArrayDequePlain<T: Copy, const CAP: usize>(ArrayDequeBase<T, CAP>);
+
+reimpl_common_methods!(ArrayDequePlain<Copy>);
+
+impl<T: Copy, const CAP: usize> ArrayDequePlain<T, CAP> {
+    #[inline(always)]
+    fn into_inner(self) -> ArrayDequeBase<T, CAP> {
+        self.0
+    }
+
+    /// Clears the buffer by resetting the indexes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDequePlain;
+    ///
+    /// let mut buf: ArrayDequePlain<_, 4> = ArrayDequePlain::new();
+    ///
+    /// buf.push_last(1).unwrap();
+    /// buf.push_first(-1).unwrap();
+    /// buf.clear();
+    ///
+    /// assert!(buf.is_empty());
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Copies all the elements of the slice to the back of the deque.
+    ///
+    /// Return `Ok` if the copy succeeds, or `Err` (copying nothing) if the slice doesn't fit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDequePlain;
+    ///
+    /// let mut buf: ArrayDequePlain<u8, 8> = ArrayDequePlain::new();
+    /// buf.extend_from_slice(b"12345").unwrap();
+    /// buf.pop_first();
+    /// buf.pop_first();
+    ///
+    /// buf.extend_from_slice(b"678").unwrap();
+    /// assert_eq!(buf.as_slices(), (&b"345678"[..], &[][..]));
+    ///
+    /// buf.extend_from_slice(b"9a").unwrap();
+    /// assert_eq!(buf.as_slices(), (&b"345678"[..], &b"9a"[..]));
+    ///
+    /// assert!(buf.extend_from_slice(b"b").is_err());
+    /// ```
+    #[inline(always)]
+    pub fn extend_from_slice(&mut self, src: &[T]) -> Result<(), &'static str> {
+        self.0.extend_from_slice(src)
+    }
+
+    /// Fills all the slots with the value, so the deque becomes full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDequePlain;
+    ///
+    /// let mut buf: ArrayDequePlain<_, 4> = ArrayDequePlain::new();
+    /// buf.push_last(1.0).unwrap();
+    ///
+    /// buf.fill(0.5);
+    /// assert_eq!(buf.len(), 4);
+    /// assert_eq!(buf, [0.5; 4]);
+    /// ```
+    #[inline]
+    pub fn fill(&mut self, value: T) {
+        self.0.arr.fill(MaybeUninit::new(value));
+        self.0.start = 0;
+        self.0.end = 0;
+        self.0.full = true;
+    }
+
+    /// Replaces the elements with the copy of the slice (with a single `memcpy`),
+    /// so the deque becomes contiguous from the beginning of the array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the slice is greater than the capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDequePlain;
+    ///
+    /// let mut buf: ArrayDequePlain<_, 4> = ArrayDequePlain::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// buf.copy_from_slice(&[3, 4, 5]);
+    /// assert_eq!(buf.as_slices(), (&[3, 4, 5][..], &[][..]));
+    /// ```
+    #[inline]
+    pub fn copy_from_slice(&mut self, src: &[T]) {
+        assert!(
+            src.len() <= CAP,
+            "source slice length ({}) exceeds the capacity ({CAP})",
+            src.len()
+        );
+        unsafe {
+            ptr::copy_nonoverlapping(src.as_ptr(), self.0.arr.as_mut_ptr().cast(), src.len());
+        }
+        self.0.start = 0;
+        unsafe { self.0.set_len(src.len()) };
+    }
+
+    /// Swaps the elements with the slice of the same length, in at most two swaps of slices.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the slice isn't equal to `len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDequePlain;
+    ///
+    /// let mut buf: ArrayDequePlain<_, 4> = ArrayDequePlain::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// let mut staging = [3, 4];
+    /// buf.swap_with_slice(&mut staging);
+    /// assert_eq!(buf, [3, 4]);
+    /// assert_eq!(staging, [1, 2]);
+    /// ```
+    #[inline]
+    pub fn swap_with_slice(&mut self, other: &mut [T]) {
+        let len = self.len();
+        assert_eq!(
+            len,
+            other.len(),
+            "destination and source slices have different lengths"
+        );
+        let (first, second) = self.as_mut_slices();
+        let (other_first, other_second) = other.split_at_mut(first.len());
+        first.swap_with_slice(other_first);
+        second.swap_with_slice(other_second);
+    }
+
+    /// Returns a copy of the first element, or `None` if empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDequePlain;
+    ///
+    /// let mut buf: ArrayDequePlain<_, 4> = ArrayDequePlain::new();
+    /// buf.push_last(1).unwrap();
+    ///
+    /// while let Some(first) = buf.first_copy() {
+    ///     if first > 3 {
+    ///         break;
+    ///     }
+    ///     buf.push_first(first + 1).unwrap();
+    /// }
+    ///
+    /// assert_eq!(buf.as_slices(), (&[4, 3, 2][..], &[1][..]));
+    /// ```
+    #[inline(always)]
+    pub fn first_copy(&self) -> Option<T> {
+        self.0.first().copied()
+    }
+
+    /// Returns a copy of the last element, or `None` if empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDequePlain;
+    ///
+    /// let mut buf: ArrayDequePlain<_, 4> = ArrayDequePlain::new();
+    /// buf.push_last(1).unwrap();
+    ///
+    /// if let Some(last) = buf.last_copy() {
+    ///     buf.push_last(last * 10).unwrap();
+    /// }
+    ///
+    /// assert_eq!(buf.last_copy(), Some(10));
+    /// ```
+    #[inline(always)]
+    pub fn last_copy(&self) -> Option<T> {
+        self.0.last().copied()
+    }
+}
+
+/// Copies the elements to the back until it's full, the rest are ignored (not taken from the iterator).
+impl<'a, T: Copy + 'a, const CAP: usize> Extend<&'a T> for ArrayDequePlain<T, CAP> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.0.extend_truncate(iter.into_iter().copied());
+    }
+}
+
+/// Takes the first `CAP` elements, the rest are ignored (not taken from the iterator).
+///
+/// # Examples
+///
+/// ```
+/// use array_buf::ArrayDequePlain;
+///
+/// let bytes: &[u8] = b"hello";
+/// let buf: ArrayDequePlain<u8, 4> = bytes.iter().collect();
+///
+/// assert_eq!(buf.as_slices(), (&b"hell"[..], &[][..]));
+/// ```
+impl<'a, T: Copy + 'a, const CAP: usize> FromIterator<&'a T> for ArrayDequePlain<T, CAP> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = &'a T>>(iter: I) -> Self {
+        let mut buf = Self::new();
+        buf.extend(iter);
+        buf
+    }
+}
+
+impl<T: Copy, const CAP: usize> IntoIterator for ArrayDequePlain<T, CAP> {
+    type Item = T;
+    type IntoIter = IntoIter<T, CAP>;
+
+    /// Consumes the deque into a front-to-back iterator yielding elements by value.
+    #[inline(always)]
+    fn into_iter(self) -> IntoIter<T, CAP> {
+        IntoIter(self.into_inner())
+    }
+}
+
+/// Copies the slice, or returns `Err` if it's longer than `CAP`.
+///
+/// # Examples
+///
+/// ```
+/// use array_buf::ArrayDequePlain;
+///
+/// let buf = ArrayDequePlain::<u8, 4>::try_from(&b"abc"[..]).unwrap();
+/// assert_eq!(buf.as_slices(), (&b"abc"[..], &[][..]));
+///
+/// assert!(ArrayDequePlain::<u8, 2>::try_from(&b"abc"[..]).is_err());
+/// ```
+impl<T: Copy, const CAP: usize> TryFrom<&[T]> for ArrayDequePlain<T, CAP> {
+    type Error = &'static str;
+
+    #[inline]
+    fn try_from(slice: &[T]) -> Result<Self, Self::Error> {
+        let mut buf = Self::new();
+        buf.extend_from_slice(slice)?;
+        Ok(buf)
+    }
+}
+
+/// A fixed capacity deque. Capacity must not be zero.
+///
+/// A power of two capacity is the fastest: indexes wrap with a bit mask,
+/// otherwise with a compare and subtract.
+/// If you have plain data, better use `ArrayDequePlain`.
+///
+/// Can be stored directly on the stack.
+///
+/// The "default" usage of this as a queue is to use `push_last` to add to
+/// the queue, and `pop_first` to consume from the queue.
+///
+/// Elements are dropped in order, from the first to the last.
+#[derive(Debug, Default)]
+#[repr(transparent)]
+pub struct ArrayDeque<T, const CAP: usize>(ArrayDequeBase<T, CAP>);
+
+impl<T: Clone, const CAP: usize> Clone for ArrayDeque<T, CAP> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+
+    /// Reuses the existing elements with `T::clone_from()` where the lengths overlap.
+    fn clone_from(&mut self, source: &Self) {
+        let common = self.len().min(source.len());
+        for (dst, src) in self.iter_mut().zip(source.iter()) {
+            dst.clone_from(src);
+        }
+        while self.len() > common {
+            drop(self.pop_last());
+        }
+        for v in source.iter().skip(common) {
+            unsafe { self.0.push_last_unchecked(v.clone()) };
+        }
+        self.0.overwrite = source.0.overwrite;
+    }
+}
+
+reimpl_common_methods!(ArrayDeque);
+
+impl<T, const CAP: usize> ArrayDeque<T, CAP> {
+    #[inline]
+    fn into_inner(self) -> ArrayDequeBase<T, CAP> {
+        let this = ManuallyDrop::new(self);
+        // `Drop` of `self` is skipped, so the values are moved only once
+        unsafe { ptr::read(&this.0) }
+    }
+
+    /// Clears the buffer by dropping (in order, from the first element) and resetting the indexes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    ///
+    /// buf.push_last(1).unwrap();
+    /// buf.push_first(-1).unwrap();
+    /// buf.clear();
+    ///
+    /// assert!(buf.is_empty());
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        self.0.drop_arr_vals();
+        self.0.clear();
+    }
+}
+
+impl<T, const CAP: usize> Drop for ArrayDeque<T, CAP> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.0.drop_arr_vals();
+    }
+}
+
+/// Clones the slice, or returns `Err` if it's longer than `CAP`.
+///
+/// # Examples
+///
+/// ```
+/// use array_buf::ArrayDeque;
+///
+/// let src = ["a".to_owned(), "b".to_owned()];
+/// let buf = ArrayDeque::<String, 4>::try_from(&src[..]).unwrap();
+/// assert!(buf.iter().eq(&src));
+///
+/// assert!(ArrayDeque::<String, 1>::try_from(&src[..]).is_err());
+/// ```
+impl<T: Clone, const CAP: usize> TryFrom<&[T]> for ArrayDeque<T, CAP> {
+    type Error = &'static str;
+
+    #[inline]
+    fn try_from(slice: &[T]) -> Result<Self, Self::Error> {
+        if slice.len() > CAP {
+            return Err("not enough capacity");
+        }
+        let mut buf = Self::new();
+        for v in slice {
+            unsafe { buf.push_last_unchecked(v.clone()) };
+        }
+        Ok(buf)
+    }
+}
+
+impl<T, const CAP: usize> IntoIterator for ArrayDeque<T, CAP> {
+    type Item = T;
+    type IntoIter = IntoIter<T, CAP>;
+
+    /// Consumes the deque into a front-to-back iterator yielding elements by value.
+    /// Elements which are not yielded are dropped with the iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last("b".to_owned()).unwrap();
+    /// buf.push_first("a".to_owned()).unwrap();
+    ///
+    /// assert!(buf.into_iter().eq(["a", "b"]));
+    /// ```
+    #[inline]
+    fn into_iter(self) -> IntoIter<T, CAP> {
+        IntoIter(self.into_inner())
+    }
+}
+
+impl<T: Copy, const CAP: usize> ArrayDeque<T, CAP> {
+    /// Converts into `ArrayDequePlain` without moving the elements one by one,
+    /// the overwrite mode is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.push_last(2).unwrap();
+    /// buf.push_first(1).unwrap();
+    ///
+    /// let plain = buf.into_plain();
+    /// assert_eq!(plain.as_slices(), (&[1][..], &[2][..]));
+    /// ```
+    #[inline(always)]
+    pub fn into_plain(self) -> ArrayDequePlain<T, CAP> {
+        ArrayDequePlain(self.into_inner())
+    }
+
+    /// Converts from `ArrayDequePlain` without moving the elements one by one,
+    /// the overwrite mode is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::{ArrayDeque, ArrayDequePlain};
+    ///
+    /// let mut plain: ArrayDequePlain<_, 4> = ArrayDequePlain::new();
+    /// plain.push_last(2).unwrap();
+    /// plain.push_first(1).unwrap();
+    ///
+    /// let buf = ArrayDeque::from_plain(plain);
+    /// assert_eq!(buf.as_slices(), (&[1][..], &[2][..]));
+    /// ```
+    #[inline(always)]
+    pub fn from_plain(plain: ArrayDequePlain<T, CAP>) -> Self {
+        Self(plain.into_inner())
+    }
+}
 
-                assert_eq!(buf.len(), 1);
-                ```
-            ")]
-            #[inline(always)]
-            pub fn len(&self) -> usize {
-                self.0.len()
-            }
+impl<T: Copy, const CAP: usize> From<ArrayDequePlain<T, CAP>> for ArrayDeque<T, CAP> {
+    #[inline(always)]
+    fn from(plain: ArrayDequePlain<T, CAP>) -> Self {
+        Self::from_plain(plain)
+    }
+}
 
-            #[doc = concat!("
-                Returns true if the array contains no elements.
+impl<T: Copy, const CAP: usize> From<ArrayDeque<T, CAP>> for ArrayDequePlain<T, CAP> {
+    #[inline(always)]
+    fn from(buf: ArrayDeque<T, CAP>) -> Self {
+        buf.into_plain()
+    }
+}
 
-                # Examples
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
 
-                ```
-                use array_buf::", stringify!($struct_name), ";
+    fn xorshift(seed: &mut u64) -> u64 {
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 7;
+        *seed ^= *seed << 17;
+        *seed
+    }
 
-                let mut buf: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
-                assert!(buf.is_empty());
+    thread_local! {
+        /// Ids of the dropped `DropCounter`s, in order (each test runs on its own thread).
+        static DROPPED: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+        /// Id of the `DropCounter` which panics when dropped (after it's logged).
+        static PANIC_ON: Cell<Option<usize>> = const { Cell::new(None) };
+    }
 
-                buf.push_last(1).unwrap();
+    /// Id of a `DropCounter`, `()` makes it zero-sized.
+    pub(crate) trait DropId {
+        fn id(&self) -> usize;
+    }
 
-                assert!(!buf.is_empty());
-                ```
-            ")]
-            #[inline(always)]
-            pub fn is_empty(&self) -> bool {
-                self.0.is_empty()
-            }
+    impl DropId for usize {
+        fn id(&self) -> usize {
+            *self
+        }
+    }
 
-            #[doc = concat!("
-                Returns true if the array is full.
+    impl DropId for () {
+        fn id(&self) -> usize {
+            0
+        }
+    }
 
-                # Examples
+    /// Logs its id when dropped, see `take_dropped()`.
+    #[derive(Clone, Debug, PartialEq)]
+    pub(crate) struct DropCounter<I: DropId = usize>(pub(crate) I);
+
+    impl<I: DropId> Drop for DropCounter<I> {
+        fn drop(&mut self) {
+            let id = self.0.id();
+            DROPPED.with(|d| d.borrow_mut().push(id));
+            if PANIC_ON.get() == Some(id) {
+                PANIC_ON.set(None);
+                panic!("DropCounter {id}");
+            }
+        }
+    }
 
-                ```
-                use array_buf::", stringify!($struct_name), ";
+    /// Takes the ids of the `DropCounter`s dropped so far (in this test).
+    pub(crate) fn take_dropped() -> Vec<usize> {
+        DROPPED.take()
+    }
 
-                let mut buf: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
-                assert!(!buf.is_full());
+    /// Number of the `DropCounter`s dropped so far (in this test).
+    pub(crate) fn dropped_count() -> usize {
+        DROPPED.with_borrow(Vec::len)
+    }
 
-                buf.push_last(1).unwrap();
-                buf.push_last(2).unwrap();
+    /// The next drop of a `DropCounter` with the `id` panics.
+    pub(crate) fn panic_on_drop(id: usize) {
+        PANIC_ON.set(Some(id));
+    }
 
-                assert!(buf.is_full());
-                ```
-            ")]
-            #[inline(always)]
-            pub fn is_full(&self) -> bool {
-                self.0.is_full()
-            }
+    /// Operation for replaying and differential testing.
+    #[derive(Clone, Debug)]
+    enum Op<T> {
+        PushFirst(T),
+        PushLast(T),
+        PopFirst,
+        PopLast,
+        Linearize,
+        Clear,
+        /// `rotate_left(n)`, `n` may be greater than the length.
+        Rotate(usize),
+    }
 
-            /// Order can be compromised once full.
-            #[inline(always)]
-            pub fn is_contiguous_any_order(&self) -> bool {
-                self.0.is_contiguous_any_order()
+    impl<T> Op<T> {
+        /// Random operation from the `seed` state.
+        fn random(seed: &mut u64, val: T) -> Self {
+            match xorshift(seed) % 18 {
+                0..=4 => Op::PushFirst(val),
+                5..=9 => Op::PushLast(val),
+                10..=11 => Op::PopFirst,
+                12..=13 => Op::PopLast,
+                14 => Op::Linearize,
+                15 => Op::Clear,
+                _ => Op::Rotate(xorshift(seed) as usize % 32),
             }
+        }
 
-            #[inline(always)]
-            pub fn is_contiguous(&self) -> bool {
-                self.0.is_contiguous()
+        /// Applies to the `VecDeque` model, which is limited by `cap`.
+        fn apply_model(self, model: &mut std::collections::VecDeque<T>, cap: usize) -> Option<T> {
+            match self {
+                Op::PushFirst(v) | Op::PushLast(v) if model.len() == cap => Some(v),
+                Op::PushFirst(v) => {
+                    model.push_front(v);
+                    None
+                }
+                Op::PushLast(v) => {
+                    model.push_back(v);
+                    None
+                }
+                Op::PopFirst => model.pop_front(),
+                Op::PopLast => model.pop_back(),
+                Op::Linearize => None,
+                Op::Clear => {
+                    model.clear();
+                    None
+                }
+                Op::Rotate(n) => {
+                    if !model.is_empty() {
+                        let len = model.len();
+                        model.rotate_left(n % len);
+                    }
+                    None
+                }
             }
+        }
+    }
 
-            /// # Safety
-            ///
-            /// Must not be empty.
-            #[inline(always)]
-            pub unsafe fn pop_first_unchecked(&mut self) -> T {
-                unsafe { self.0.pop_first_unchecked() }
+    impl<T, const CAP: usize> ArrayDeque<T, CAP> {
+        /// Returns a popped or a rejected element.
+        fn apply(&mut self, op: Op<T>) -> Option<T> {
+            match op {
+                Op::PushFirst(v) | Op::PushLast(v) if self.is_full() => Some(v),
+                Op::PushFirst(v) => {
+                    self.push_first(v).unwrap();
+                    None
+                }
+                Op::PushLast(v) => {
+                    self.push_last(v).unwrap();
+                    None
+                }
+                Op::PopFirst => self.pop_first(),
+                Op::PopLast => self.pop_last(),
+                Op::Linearize => {
+                    self.linearize();
+                    None
+                }
+                Op::Clear => {
+                    self.clear();
+                    None
+                }
+                Op::Rotate(n) => {
+                    self.rotate_left(n);
+                    None
+                }
             }
+        }
 
-            /// # Safety
-            ///
-            /// Must not be empty.
-            #[inline(always)]
-            pub unsafe fn pop_last_unchecked(&mut self) -> T {
-                unsafe { self.0.pop_last_unchecked() }
+        fn replay(&mut self, ops: &[Op<T>])
+        where
+            T: Clone,
+        {
+            for op in ops {
+                self.apply(op.clone());
             }
+        }
+    }
 
-            #[doc = concat!("
-                Removes the first element and returns it, or `None` if empty.
+    #[test]
+    fn test_copy() {
+        let mut a = ArrayDequePlain::<i32, 8>::new();
+        a.push_last(1).unwrap();
+        a.push_last(2).unwrap();
 
-                # Examples
+        // contiguous
+        let mut b = a;
+        assert_eq!(a.pop_first(), Some(1));
+        assert_eq!(a.len(), 1);
+        assert_eq!(b.len(), 2);
+        assert_eq!(b.pop_first(), Some(1));
 
-                ```
-                use array_buf::", stringify!($struct_name), ";
+        // not contiguous
+        a.push_first(-1).unwrap();
+        a.push_first(-2).unwrap();
+        let mut c = a;
+        assert_eq!(a.pop_first(), Some(-2));
+        assert_eq!(a.len(), 2);
+        assert_eq!(c.len(), 3);
+        assert_eq!(c.pop_first(), Some(-2));
+        assert_eq!(b.len(), 1);
+    }
 
-                let mut buf: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
-                assert_eq!(buf.pop_first(), None);
+    #[test]
+    fn test_clone() {
+        let mut a = ArrayDeque::<String, 8>::new();
+        a.push_last("1".to_owned()).unwrap();
 
-                buf.push_last(1).unwrap();
-                buf.push_last(2).unwrap();
+        // contiguous
+        let mut b = a.clone();
+        a.first_mut().unwrap().push('x');
+        assert_eq!(a.pop_first(), Some("1x".to_owned()));
+        assert_eq!(b.first().map(|s| s.as_str()), Some("1"));
 
-                assert_eq!(buf.pop_first(), Some(1));
-                assert_eq!(buf.pop_first(), Some(2));
-                assert_eq!(buf.pop_first(), None);
-                ```
-            ")]
-            #[inline(always)]
-            pub fn pop_first(&mut self) -> Option<T> {
-                self.0.pop_first()
+        // not contiguous
+        b.push_first("-1".to_owned()).unwrap();
+        let c = b.clone();
+        b.first_mut().unwrap().push('x');
+        assert_eq!(b.pop_first(), Some("-1x".to_owned()));
+        assert_eq!(c.first().map(|s| s.as_str()), Some("-1"));
+    }
+
+    #[test]
+    fn test_clone_plain_differential() {
+        fn to_vec(slices: (&[i32], &[i32])) -> Vec<i32> {
+            slices.0.iter().chain(slices.1).copied().collect()
+        }
+
+        for offset in 0..8 {
+            for len in [1, 3, 7, 8] {
+                let mut a = ArrayDeque::<i32, 8>::new();
+                let mut p = ArrayDequePlain::<i32, 8>::new();
+                // move `start` by `offset`
+                for _ in 0..offset {
+                    a.push_last(0).unwrap();
+                    a.pop_first().unwrap();
+                    p.push_last(0).unwrap();
+                    p.pop_first().unwrap();
+                }
+                for i in 0..len {
+                    a.push_last(i).unwrap();
+                    p.push_last(i).unwrap();
+                }
+
+                let mut a2 = a.clone();
+                let mut p2 = p;
+                assert_eq!(to_vec(a2.as_slices()), to_vec(p2.as_slices()));
+                assert_eq!(to_vec(a2.as_slices()), to_vec(a.as_slices()));
+                assert_eq!(a2.is_full(), p2.is_full());
+
+                *a2.first_mut().unwrap() = -1;
+                *p2.first_mut().unwrap() = -1;
+                assert_eq!(a.first(), Some(&0));
+                assert_eq!(p.first(), Some(&0));
+
+                a2.pop_last().unwrap();
+                p2.pop_last().unwrap();
+                assert_eq!(a.len(), len as usize);
+                assert_eq!(p.len(), len as usize);
+                assert_eq!(to_vec(a2.as_slices()), to_vec(p2.as_slices()));
             }
+        }
+    }
 
-            #[doc = concat!("
-                Removes the last element and returns it, or `None` if empty.
+    #[cfg(feature = "stable_hash")]
+    #[test]
+    fn test_stable_hash_known_answer() {
+        let mut buf = ArrayDeque::<u8, 4>::new();
+        buf.push_last(3).unwrap();
+        buf.push_last(4).unwrap();
+        buf.push_first(2).unwrap();
+        buf.push_first(1).unwrap();
+        assert!(!buf.is_contiguous());
+
+        assert_eq!(buf.stable_hash(), 0xbe7a5e775165785d);
+    }
 
-                # Examples
+    #[test]
+    fn test_push_front_placeholder_wrapped() {
+        let mut buf: ArrayDeque<i32, 4> = ArrayDeque::new();
+        buf.push_last(2).unwrap();
+        buf.push_first(1).unwrap();
+
+        let slots = unsafe { buf.push_front_placeholder(2) }.unwrap();
+        slots[0] = MaybeUninit::new(-1);
+        slots[1] = MaybeUninit::new(0);
+
+        assert!(buf.is_full());
+        assert!(unsafe { buf.push_front_placeholder(1) }.is_err());
+        assert_eq!(buf.pop_first(), Some(-1));
+        assert_eq!(buf.pop_first(), Some(0));
+        assert_eq!(buf.pop_first(), Some(1));
+        assert_eq!(buf.pop_first(), Some(2));
+    }
 
-                ```
-                use array_buf::", stringify!($struct_name), ";
+    #[test]
+    fn test_take_all_drop() {
+        use std::rc::Rc;
+
+        let rc = Rc::new(());
+        let mut buf: ArrayDeque<Rc<()>, 4> = ArrayDeque::new();
+        buf.push_last(rc.clone()).unwrap();
+        buf.push_first(rc.clone()).unwrap();
+        buf.push_first(rc.clone()).unwrap();
+        assert_eq!(Rc::strong_count(&rc), 4);
+
+        let mut iter = buf.take_all();
+        assert!(buf.is_empty());
+        assert_eq!(iter.len(), 3);
+        drop(iter.next());
+        assert_eq!(Rc::strong_count(&rc), 3);
+
+        drop(iter);
+        assert_eq!(Rc::strong_count(&rc), 1);
+        drop(buf);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
 
-                let mut buf: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
-                assert_eq!(buf.pop_last(), None);
+    #[test]
+    fn test_binary_search_rotated() {
+        let sorted = [1, 3, 5, 7, 9, 11];
+        for rot in 0..sorted.len() {
+            for offset in 0..8 {
+                let mut buf: ArrayDequePlain<i32, 8> = ArrayDequePlain::new();
+                for _ in 0..offset {
+                    buf.push_last(0).unwrap();
+                    buf.pop_first().unwrap();
+                }
+                for i in 0..sorted.len() {
+                    buf.push_last(sorted[(i + rot) % sorted.len()]).unwrap();
+                }
+
+                for (i, v) in sorted.iter().enumerate() {
+                    let logical = (i + sorted.len() - rot) % sorted.len();
+                    assert_eq!(buf.binary_search_rotated(v), Ok(logical));
+                }
+                for v in [0, 2, 4, 6, 8, 10, 12] {
+                    let pos = buf.binary_search_rotated(&v).unwrap_err();
+                    let mut inserted = buf.as_slices().0.to_vec();
+                    inserted.extend_from_slice(buf.as_slices().1);
+                    inserted.insert(pos, v);
+                    // still a rotated sorted sequence
+                    let descents = (0..inserted.len())
+                        .filter(|&i| inserted[i] > inserted[(i + 1) % inserted.len()])
+                        .count();
+                    assert_eq!(descents, 1, "rot {rot} v {v} pos {pos}");
+                }
+            }
+        }
+    }
 
-                buf.push_last(1).unwrap();
-                buf.push_last(2).unwrap();
+    fn check_ops<const CAP: usize>(seed: u64, steps: usize) {
+        let mut seed = seed;
+        let mut buf: ArrayDeque<usize, CAP> = ArrayDeque::new();
+        let mut model = std::collections::VecDeque::new();
+        let mut ops = Vec::new();
+        for i in 0..steps {
+            let op = Op::random(&mut seed, i);
+            ops.push(op.clone());
+            assert_eq!(
+                buf.apply(op.clone()),
+                op.apply_model(&mut model, CAP),
+                "{ops:?}"
+            );
+
+            let (a, b) = buf.as_slices();
+            assert!(a.iter().chain(b).eq(model.iter()), "{ops:?}");
+            assert_eq!(buf.len(), model.len());
+        }
 
-                assert_eq!(buf.pop_last(), Some(2));
-                assert_eq!(buf.pop_last(), Some(1));
-                assert_eq!(buf.pop_last(), None);
-                ```
-            ")]
-            #[inline(always)]
-            pub fn pop_last(&mut self) -> Option<T> {
-                self.0.pop_last()
+        let mut replayed: ArrayDeque<usize, CAP> = ArrayDeque::new();
+        replayed.replay(&ops);
+        assert_eq!(replayed.as_slices(), buf.as_slices());
+    }
+
+    #[test]
+    fn test_ops_differential() {
+        for seed in 1..=16 {
+            check_ops::<2>(seed, 200);
+            check_ops::<4>(seed, 200);
+            check_ops::<8>(seed, 500);
+            check_ops::<3>(seed, 200);
+            check_ops::<10>(seed, 500);
+        }
+    }
+
+    #[test]
+    fn test_cap_one() {
+        use std::rc::Rc;
+
+        let mut buf: ArrayDeque<i32, 1> = ArrayDeque::new();
+        assert!(buf.is_empty());
+        assert!(!buf.is_full());
+        assert_eq!(buf.as_slices(), (&[][..], &[][..]));
+        assert_eq!(buf.first(), None);
+
+        buf.push_last(1).unwrap();
+        assert!(buf.is_full());
+        assert_eq!(buf.len(), 1);
+        assert!(buf.push_last(2).is_err());
+        assert!(buf.push_first(2).is_err());
+        assert_eq!(buf.first(), Some(&1));
+        assert_eq!(buf.last(), Some(&1));
+        assert_eq!(buf.as_slices(), (&[1][..], &[][..]));
+
+        // refill after popping
+        assert_eq!(buf.pop_first(), Some(1));
+        buf.push_first(2).unwrap();
+        assert_eq!(buf.as_slices(), (&[2][..], &[][..]));
+        assert_eq!(buf.pop_last(), Some(2));
+        assert!(buf.is_empty());
+        assert_eq!(buf.pop_last(), None);
+
+        // overwrite, the evicted value is dropped
+        let (a, b) = (Rc::new(1), Rc::new(2));
+        let mut buf: ArrayDeque<Rc<i32>, 1> = ArrayDeque::new();
+        buf.set_overwrite(true);
+        buf.push_last(a.clone()).unwrap();
+        buf.push_last(b.clone()).unwrap();
+        assert_eq!(buf.len(), 1);
+        assert_eq!(Rc::strong_count(&a), 1);
+        assert_eq!(buf.first(), Some(&b));
+        buf.push_first(a.clone()).unwrap();
+        buf.push_first(a.clone()).unwrap();
+        assert_eq!(buf.len(), 1);
+        assert_eq!(Rc::strong_count(&b), 1);
+        assert_eq!(Rc::strong_count(&a), 2);
+        assert_eq!(buf.first(), Some(&a));
+        drop(buf);
+        assert_eq!(Rc::strong_count(&a), 1);
+
+        let mut plain: ArrayDequePlain<i32, 1> = ArrayDequePlain::new();
+        plain.push_first(3).unwrap();
+        let copy = plain;
+        assert_eq!(plain.pop_first(), Some(3));
+        assert_eq!(copy.as_slices(), (&[3][..], &[][..]));
+    }
+
+    #[test]
+    fn test_overwrite_toggle() {
+        use std::rc::Rc;
+
+        let rc = Rc::new(());
+        let mut buf: ArrayDeque<Rc<()>, 4> = ArrayDeque::new();
+        for _ in 0..4 {
+            buf.push_last(rc.clone()).unwrap();
+        }
+        assert!(buf.push_last(rc.clone()).is_err());
+        assert!(buf.push_first(rc.clone()).is_err());
+        assert_eq!(Rc::strong_count(&rc), 5);
+
+        buf.set_overwrite(true);
+        for _ in 0..6 {
+            buf.push_last(rc.clone()).unwrap();
+            buf.push_first(rc.clone()).unwrap();
+        }
+        assert_eq!(buf.len(), 4);
+        assert_eq!(Rc::strong_count(&rc), 5);
+
+        buf.set_overwrite(false);
+        assert!(buf.push_last(rc.clone()).is_err());
+        buf.pop_first().unwrap();
+        buf.push_last(rc.clone()).unwrap();
+        assert!(!buf.is_overwrite());
+
+        drop(buf);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    fn check_slices_consistency<const CAP: usize>() {
+        for start in 0..CAP {
+            for len in 0..=CAP {
+                for front in 0..=len {
+                    let mut buf: ArrayDequePlain<usize, CAP> = ArrayDequePlain::new();
+                    for _ in 0..start {
+                        buf.push_last(0).unwrap();
+                        buf.pop_first().unwrap();
+                    }
+                    // `front` elements are pushed to the start
+                    for i in front..len {
+                        buf.push_last(i).unwrap();
+                    }
+                    for i in (0..front).rev() {
+                        buf.push_first(i).unwrap();
+                    }
+                    assert_eq!(buf.is_full(), len == CAP);
+
+                    let (a, b) = buf.as_slices();
+                    assert_eq!(a.len() + b.len(), buf.len());
+                    assert!(a.iter().chain(b).copied().eq(buf.clone().take_all()));
+                    assert!(a.iter().chain(b).eq(buf.iter()));
+                    assert!(a.iter().chain(b).rev().eq(buf.iter().rev()));
+                    assert_eq!(buf.iter().len(), buf.len());
+                    assert!(a.iter().chain(b).copied().eq(0..len));
+                }
             }
+        }
+    }
 
-            /// # Safety
-            ///
-            /// Must not be full.
-            #[inline(always)]
-            pub unsafe fn push_first_unchecked(&mut self, element: T) {
-                unsafe { self.0.push_first_unchecked(element) }
+    #[test]
+    fn test_slices_consistency() {
+        check_slices_consistency::<2>();
+        check_slices_consistency::<4>();
+        check_slices_consistency::<8>();
+        check_slices_consistency::<16>();
+        check_slices_consistency::<3>();
+        check_slices_consistency::<10>();
+    }
+
+    #[test]
+    fn test_drop_order() {
+        let fill = |buf: &mut ArrayDeque<DropCounter, 4>| {
+            buf.push_last(DropCounter(2)).unwrap();
+            buf.push_last(DropCounter(3)).unwrap();
+            buf.push_first(DropCounter(1)).unwrap();
+            buf.push_first(DropCounter(0)).unwrap();
+        };
+
+        let mut buf = ArrayDeque::new();
+        fill(&mut buf);
+        buf.clear();
+        assert_eq!(take_dropped(), [0, 1, 2, 3]);
+
+        fill(&mut buf);
+        drop(buf);
+        assert_eq!(take_dropped(), [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_logical_pairs_reverse() {
+        for offset in 0..8 {
+            for len in 0..=8 {
+                let mut buf: ArrayDeque<usize, 8> = ArrayDeque::new();
+                for _ in 0..offset {
+                    buf.push_last(0).unwrap();
+                    buf.pop_first().unwrap();
+                }
+                for i in 0..len {
+                    buf.push_last(i).unwrap();
+                }
+
+                buf.for_each_logical_pair_mut(mem::swap);
+
+                let (a, b) = buf.as_slices();
+                assert!(a.iter().chain(b).copied().eq((0..len).rev()));
             }
+        }
+    }
 
-            /// # Safety
-            ///
-            /// Must not be full.
-            #[inline(always)]
-            pub unsafe fn push_last_unchecked(&mut self, element: T) {
-                unsafe { self.0.push_last_unchecked(element) }
+    fn check_linearize_large<const CAP: usize>() {
+        use std::rc::Rc;
+
+        struct Large(Rc<usize>, [u64; 32]);
+
+        for start in 0..CAP {
+            for len in 0..=CAP {
+                let rc = Rc::new(0);
+                let mut buf: ArrayDeque<Large, CAP> = ArrayDeque::new();
+                for _ in 0..start {
+                    buf.push_last(Large(rc.clone(), [0; 32])).unwrap();
+                    buf.pop_first().unwrap();
+                }
+                for i in 0..len {
+                    buf.push_last(Large(rc.clone(), [i as u64; 32])).unwrap();
+                }
+
+                buf.linearize();
+
+                assert!(buf.is_contiguous_any_order());
+                let (a, b) = buf.as_slices();
+                assert!(b.is_empty());
+                assert!(a.iter().map(|v| v.1[31] as usize).eq(0..len));
+                assert!(a.iter().all(|v| Rc::ptr_eq(&v.0, &rc)));
+                drop(buf);
+                assert_eq!(Rc::strong_count(&rc), 1);
             }
+        }
+    }
 
-            #[doc = concat!("
-                Add an element to the start of the deque.
+    #[test]
+    fn test_linearize_large() {
+        check_linearize_large::<16>();
+        check_linearize_large::<12>();
+        check_linearize_large::<7>();
+    }
+
+    /// Moves `at..` into a new deque, then appends it back.
+    fn split_append<T, const CAP: usize>(buf: &mut ArrayDeque<T, CAP>, at: usize) {
+        let mut tail = buf.split_off(at);
+        assert_eq!(buf.len(), at);
+        buf.append(&mut tail).unwrap();
+        assert!(tail.is_empty());
+    }
+
+    fn check_split_append<const CAP: usize>(seed: &mut u64) {
+        let offset = xorshift(seed) as usize % CAP;
+        let len = xorshift(seed) as usize % (CAP + 1);
+        let at = xorshift(seed) as usize % (len + 1);
 
-                Return `Ok` if the push succeeds, or `Err` if the array is full.
+        let mut buf: ArrayDeque<DropCounter, CAP> = ArrayDeque::new();
+        for _ in 0..offset {
+            buf.push_last(DropCounter(usize::MAX)).unwrap();
+            buf.pop_first().unwrap();
+        }
+        take_dropped();
+        for i in 0..len {
+            buf.push_last(DropCounter(i)).unwrap();
+        }
 
-                # Examples
+        split_append(&mut buf, at);
+        split_append(&mut buf, 0);
+        split_append(&mut buf, len);
 
-                ```
-                use array_buf::", stringify!($struct_name), ";
+        let (a, b) = buf.as_slices();
+        assert!(a.iter().chain(b).map(|v| v.0).eq(0..len));
+        assert_eq!(dropped_count(), 0);
+        drop(buf);
+        assert!(take_dropped().into_iter().eq(0..len));
+    }
 
-                let mut buf: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
+    #[test]
+    fn test_split_append_round_trip() {
+        let mut seed = 42;
+        for _ in 0..100 {
+            check_split_append::<1>(&mut seed);
+            check_split_append::<2>(&mut seed);
+            check_split_append::<4>(&mut seed);
+            check_split_append::<8>(&mut seed);
+            check_split_append::<16>(&mut seed);
+            check_split_append::<3>(&mut seed);
+            check_split_append::<12>(&mut seed);
+        }
+    }
 
-                buf.push_first(-1);
-                buf.push_first(-2);
+    #[test]
+    fn test_index_wrapped() {
+        let mut buf: ArrayDeque<i32, 8> = ArrayDeque::new();
+        for _ in 0..6 {
+            buf.push_last(0).unwrap();
+            buf.pop_first().unwrap();
+        }
+        for i in 0..5 {
+            buf.push_last(i).unwrap();
+        }
+        assert!(!buf.is_contiguous());
 
-                let overflow = buf.push_first(-3);
+        for i in 0..5 {
+            assert_eq!(buf[i as usize], i);
+            buf[i as usize] *= 10;
+        }
+        assert_eq!(buf.as_slices(), (&[0, 10][..], &[20, 30, 40][..]));
+    }
 
-                assert!(overflow.is_err());
-                assert_eq!(buf.first(), Some(&-2));
-                ```
-            ")]
-            #[inline(always)]
-            pub fn push_first(&mut self, element: T) -> Result<(), &'static str> {
-                self.0.push_first(element)
-            }
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_index_out_of_bounds() {
+        let mut buf: ArrayDequePlain<i32, 4> = ArrayDequePlain::new();
+        buf.push_last(1).unwrap();
+        let _ = buf[1];
+    }
 
-            #[doc = concat!("
-                Add an element to the end of the deque.
+    #[test]
+    fn test_iter_mut_wrapped() {
+        let mut buf: ArrayDeque<i32, 8> = ArrayDeque::new();
+        for i in 0..4 {
+            buf.push_last(i).unwrap();
+            buf.push_first(-i - 1).unwrap();
+        }
+        assert!(!buf.is_contiguous());
 
-                Return `Ok` if the push succeeds, or `Err` if the array is full.
+        let mut iter = buf.iter_mut();
+        assert_eq!(iter.len(), 8);
+        *iter.next_back().unwrap() = 100;
+        for v in iter {
+            *v += 1;
+        }
 
-                # Examples
+        for i in -4..3 {
+            assert_eq!(buf.pop_first(), Some(i + 1));
+        }
+        assert_eq!(buf.pop_first(), Some(100));
+        assert_eq!(buf.pop_first(), None);
+    }
 
-                ```
-                use array_buf::", stringify!($struct_name), ";
+    #[test]
+    fn test_into_iter_drop() {
+        use std::rc::Rc;
+
+        let rc = Rc::new(());
+        let mut buf: ArrayDeque<Rc<()>, 8> = ArrayDeque::new();
+        for _ in 0..3 {
+            buf.push_last(rc.clone()).unwrap();
+            buf.push_first(rc.clone()).unwrap();
+        }
+        assert_eq!(Rc::strong_count(&rc), 7);
 
-                let mut buf: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
+        let mut iter = buf.into_iter();
+        for _ in 0..3 {
+            drop(iter.next().unwrap());
+        }
+        assert_eq!(iter.len(), 3);
+        assert_eq!(Rc::strong_count(&rc), 4);
 
-                buf.push_last(1);
-                buf.push_last(2);
+        drop(iter);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
 
-                let overflow = buf.push_last(3);
+    #[test]
+    fn test_into_iter_refs() {
+        let mut buf: ArrayDequePlain<i32, 4> = ArrayDequePlain::new();
+        buf.push_last(2).unwrap();
+        buf.push_first(1).unwrap();
 
-                assert!(overflow.is_err());
-                assert_eq!(buf.last(), Some(&2));
-                ```
-            ")]
-            #[inline(always)]
-            pub fn push_last(&mut self, element: T) -> Result<(), &'static str> {
-                self.0.push_last(element)
-            }
+        for v in &mut buf {
+            *v += 1;
+        }
+        let mut sum = 0;
+        for v in &buf {
+            sum += v;
+        }
+        assert_eq!(sum, 5);
+    }
 
-            #[doc = concat!("
-                Provides a reference to the first element, or `None` if empty.
+    #[test]
+    fn test_from_iter_lazy() {
+        let mut taken = 0;
+        let mut iter = (0..100).inspect(|_| taken += 1);
+        let buf: ArrayDeque<i32, 4> = iter.by_ref().collect();
+
+        assert!(buf.iter().copied().eq(0..4));
+        assert_eq!(iter.next(), Some(4));
+        drop(iter);
+        assert_eq!(taken, 5);
+    }
 
-                # Examples
+    #[test]
+    fn test_extend_half_full() {
+        let src = [3, 4, 5, 6, 7];
+        let mut buf: ArrayDequePlain<i32, 4> = ArrayDequePlain::new();
+        buf.push_last(2).unwrap();
+        buf.push_first(1).unwrap();
+
+        let mut iter = src.iter();
+        buf.extend(iter.by_ref());
+        assert!(buf.iter().eq(&[1, 2, 3, 4]));
+        assert_eq!(iter.as_slice(), &[5, 6, 7]);
+
+        let mut owned: ArrayDeque<String, 4> = ArrayDeque::new();
+        owned.push_last("a".to_owned()).unwrap();
+        let mut iter = src.iter().map(|v| v.to_string());
+        owned.extend(iter.by_ref());
+        assert!(owned.iter().eq(["a", "3", "4", "5"]));
+        assert_eq!(iter.next().as_deref(), Some("6"));
+    }
 
-                ```
-                use array_buf::", stringify!($struct_name), ";
+    #[test]
+    fn test_extend_from_slice_offsets() {
+        let src: Vec<usize> = (0..8).collect();
+        for offset in 0..8 {
+            for len in 0..=8 {
+                for add in 0..=8 {
+                    let mut buf: ArrayDequePlain<usize, 8> = ArrayDequePlain::new();
+                    for _ in 0..offset {
+                        buf.push_last(0).unwrap();
+                        buf.pop_first().unwrap();
+                    }
+                    buf.extend_from_slice(&src[..len]).unwrap();
+
+                    let res = buf.extend_from_slice(&src[..add]);
+                    assert_eq!(res.is_ok(), len + add <= 8);
+                    let expected: Vec<usize> = if res.is_ok() {
+                        src[..len].iter().chain(&src[..add]).copied().collect()
+                    } else {
+                        src[..len].to_vec()
+                    };
+                    assert!(buf.iter().eq(&expected));
+                    assert_eq!(buf.is_full(), expected.len() == 8);
+                }
+            }
+        }
+    }
 
-                let mut buf: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
-                assert_eq!(buf.first(), None);
+    #[test]
+    fn test_from_array_drop() {
+        use std::rc::Rc;
+
+        let rc = Rc::new(());
+        let buf = ArrayDeque::from_array([rc.clone(), rc.clone()]);
+        assert_eq!(Rc::strong_count(&rc), 3);
+        drop(buf);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
 
-                buf.push_last(1).unwrap();
-                buf.push_last(2).unwrap();
+    #[test]
+    fn test_into_array_drop() {
+        use std::rc::Rc;
 
-                assert_eq!(buf.first(), Some(&1));
-                ```
-            ")]
-            #[inline(always)]
-            pub fn first(&self) -> Option<&T> {
-                self.0.first()
-            }
+        let rc = Rc::new(());
+        let mut buf: ArrayDeque<Rc<()>, 4> = ArrayDeque::new();
+        for _ in 0..3 {
+            buf.push_first(rc.clone()).unwrap();
+        }
+        let mut buf = buf.into_array().unwrap_err();
+        assert_eq!(Rc::strong_count(&rc), 4);
+
+        buf.push_first(rc.clone()).unwrap();
+        let arr = buf.into_array().unwrap();
+        assert_eq!(Rc::strong_count(&rc), 5);
+        drop(arr);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
 
-            #[doc = concat!("
-                Provides a mut reference to the first element, or `None` if empty.
+    #[test]
+    fn test_eq_rotated() {
+        let mut a: ArrayDeque<i32, 8> = ArrayDeque::new();
+        for i in 0..5 {
+            a.push_last(i).unwrap();
+        }
 
-                # Examples
+        for offset in 0..8 {
+            let mut b: ArrayDeque<i32, 8> = ArrayDeque::new();
+            for _ in 0..offset {
+                b.push_first(0).unwrap();
+                b.pop_last().unwrap();
+            }
+            for i in (0..5).rev() {
+                b.push_first(i).unwrap();
+            }
+            assert_eq!(a, b);
 
-                ```
-                use array_buf::ArrayDeque;
+            b.pop_last();
+            assert_ne!(a, b);
+            b.push_last(-1).unwrap();
+            assert_ne!(a, b);
+        }
+    }
 
-                let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
-                assert_eq!(buf.first_mut(), None);
+    #[test]
+    fn test_hash_rotated() {
+        use std::hash::{BuildHasher, RandomState};
 
-                buf.push_last(1).unwrap();
-                buf.push_last(2).unwrap();
+        let mut a: ArrayDequePlain<i32, 4> = ArrayDequePlain::new();
+        a.push_last(1).unwrap();
+        a.push_last(2).unwrap();
+        a.push_last(3).unwrap();
 
-                assert_eq!(buf.first_mut(), Some(&mut 1));
-                ```
-            ")]
-            #[inline]
-            pub fn first_mut(&mut self) -> Option<&mut T> {
-                self.0.first_mut()
-            }
+        let mut b: ArrayDequePlain<i32, 4> = ArrayDequePlain::new();
+        b.push_last(3).unwrap();
+        b.push_first(2).unwrap();
+        b.push_first(1).unwrap();
+        assert_ne!(a.as_slices(), b.as_slices());
+        assert_eq!(a, b);
 
-            #[doc = concat!("
-                Provides a reference to the last element, or `None` if empty.
+        let state = RandomState::new();
+        assert_eq!(state.hash_one(a), state.hash_one(b));
 
-                # Examples
+        let mut map = std::collections::HashMap::new();
+        map.insert(a, "a");
+        assert_eq!(map.get(&b), Some(&"a"));
+    }
 
-                ```
-                use array_buf::", stringify!($struct_name), ";
+    #[test]
+    fn test_ord() {
+        let a: ArrayDequePlain<i32, 4> = [1, 2, 3].iter().collect();
+        let mut b: ArrayDequePlain<i32, 4> = ArrayDequePlain::new();
+        b.push_last(4).unwrap();
+        b.push_first(2).unwrap();
+        b.push_first(1).unwrap();
+        assert!(!b.as_slices().1.is_empty());
+        assert!(a < b);
+        assert_eq!(a.cmp(&b), Ordering::Less);
+
+        let c: ArrayDequePlain<i32, 4> = [1, 2].iter().collect();
+        assert!(c < a);
+        assert_eq!(a.partial_cmp(&c), Some(Ordering::Greater));
+        assert_eq!(a.cmp(&a.clone()), Ordering::Equal);
+    }
 
-                let mut buf: ", stringify!($struct_name), "<_, 2> = ", stringify!($struct_name), "::new();
-                assert_eq!(buf.last(), None);
+    #[test]
+    fn test_eq_slice_wrapped() {
+        let mut buf: ArrayDeque<String, 4> = ArrayDeque::new();
+        buf.push_last("c".to_string()).unwrap();
+        buf.push_first("b".to_string()).unwrap();
+        buf.push_first("a".to_string()).unwrap();
+        assert!(!buf.as_slices().1.is_empty());
+
+        assert_eq!(buf, ["a", "b", "c"]);
+        assert_eq!(buf, &["a", "b", "c"]);
+        assert_eq!(buf, &["a", "b", "c"][..]);
+        assert_eq!(["a", "b", "c"], buf);
+        assert_ne!(buf, ["a", "c", "b"]);
+        assert_ne!(buf, ["a", "b"]);
+        assert_ne!(buf, ["a", "b", "c", "d"]);
+
+        let empty: ArrayDeque<String, 4> = ArrayDeque::new();
+        assert_eq!(empty, [] as [&str; 0]);
+    }
 
-                buf.push_last(1).unwrap();
-                buf.push_last(2).unwrap();
+    #[test]
+    fn test_position_wrapped() {
+        let mut buf: ArrayDeque<i32, 8> = ArrayDeque::new();
+        for v in [4, 5, 6, 5] {
+            buf.push_last(v).unwrap();
+        }
+        for v in [3, 2, 5] {
+            buf.push_first(v).unwrap();
+        }
+        assert!(!buf.as_slices().1.is_empty());
+        // 5, 2, 3, 4, 5, 6, 5
+
+        let i = buf.position(|v| *v == 4).unwrap();
+        assert_eq!(buf.get(i), Some(&4));
+        assert_eq!(buf.position(|v| *v == 5), Some(0));
+        assert_eq!(buf.rposition(|v| *v == 5), Some(6));
+        let i = buf.rposition(|v| *v < 4).unwrap();
+        assert_eq!((i, buf.get(i)), (2, Some(&3)));
+        assert_eq!(buf.position(|v| *v == 7), None);
+        assert_eq!(buf.rposition(|v| *v == 7), None);
+    }
 
-                assert_eq!(buf.last(), Some(&2));
-                ```
-            ")]
-            #[inline(always)]
-            pub fn last(&self) -> Option<&T> {
-                self.0.last()
+    #[test]
+    fn test_binary_search_all_rotations() {
+        for shift in 0..8 {
+            let mut buf: ArrayDeque<i32, 8> = ArrayDeque::new();
+            for _ in 0..shift {
+                buf.push_last(0).unwrap();
+                buf.pop_first();
             }
+            for v in [10, 20, 30, 40, 50] {
+                buf.push_last(v).unwrap();
+            }
+            for (i, v) in [10, 20, 30, 40, 50].iter().enumerate() {
+                assert_eq!(buf.binary_search(v), Ok(i));
+                assert_eq!(buf.binary_search(&(v - 5)), Err(i));
+            }
+            assert_eq!(buf.binary_search(&55), Err(5));
+        }
+    }
 
-            #[doc = concat!("
-                Provides a mut reference to the last element, or `None` if empty.
-
-                # Examples
+    #[test]
+    fn test_retain_drop() {
+        let mut buf: ArrayDeque<DropCounter, 8> = ArrayDeque::new();
+        for i in 3..7 {
+            buf.push_last(DropCounter(i)).unwrap();
+        }
+        for i in (0..3).rev() {
+            buf.push_first(DropCounter(i)).unwrap();
+        }
+        assert!(!buf.as_slices().1.is_empty());
 
-                ```
-                use array_buf::ArrayDeque;
+        buf.retain(|r| r.0 % 3 != 1);
+        let mut dropped = take_dropped();
+        dropped.sort();
+        assert_eq!(dropped, [1, 4]);
+        assert!(buf.iter().map(|r| r.0).eq([0, 2, 3, 5, 6]));
 
-                let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
-                assert_eq!(buf.last_mut(), None);
+        buf.retain_mut(|_| true);
+        assert_eq!(dropped_count(), 0);
+        assert_eq!(buf.len(), 5);
 
-                buf.push_last(1).unwrap();
-                buf.push_last(2).unwrap();
+        buf.retain(|_| false);
+        assert!(buf.is_empty());
+        assert_eq!(dropped_count(), 5);
+    }
 
-                assert_eq!(buf.last_mut(), Some(&mut 2));
-                ```
-            ")]
-            #[inline]
-            pub fn last_mut(&mut self) -> Option<&mut T> {
-                self.0.last_mut()
+    #[test]
+    fn test_remove_all_positions() {
+        for offset in 0..8 {
+            for len in 1..=8 {
+                for index in 0..len {
+                    let mut buf: ArrayDeque<usize, 8> = ArrayDeque::new();
+                    for _ in 0..offset {
+                        buf.push_last(0).unwrap();
+                        buf.pop_first();
+                    }
+                    for i in 0..len {
+                        buf.push_last(i).unwrap();
+                    }
+                    assert_eq!(buf.remove(index), Some(index));
+                    assert_eq!(buf.len(), len - 1);
+                    assert!(buf.iter().copied().eq((0..len).filter(|&i| i != index)));
+                    let (a, b) = buf.as_slices();
+                    assert!(a.iter().chain(b).eq(buf.iter()));
+                    buf.push_last(len).unwrap();
+                    assert_eq!(buf.last(), Some(&len));
+                }
             }
+        }
+    }
 
-            /// Returns a slice which contains the content of the inner buffer.
-            ///
-            /// # Safety
-            ///
-            /// Must be contiguous. If it's not, use `linearize()`.
-            #[inline(always)]
-            pub unsafe fn as_slice(&self) -> &[T] {
-                unsafe { self.0.as_slice() }
+    #[test]
+    fn test_swap_remove_wrapped() {
+        let fill = || {
+            let mut buf: ArrayDeque<i32, 8> = ArrayDeque::new();
+            for v in [3, 4, 5] {
+                buf.push_last(v).unwrap();
             }
-
-            /// Returns a slice which contains the content of the inner buffer.
-            ///
-            /// # Safety
-            ///
-            /// Must be contiguous. If it's not, use `linearize()`.
-            #[inline(always)]
-            pub unsafe fn as_mut_slice(&mut self) -> &mut [T] {
-                unsafe { self.0.as_mut_slice() }
+            for v in [2, 1, 0] {
+                buf.push_first(v).unwrap();
             }
+            assert!(!buf.as_slices().1.is_empty());
+            buf
+        };
 
-            #[doc = concat!("
-                Returns a pair of slices which contain, in order, the contents of the
-                inner buffer.
+        let mut buf = fill();
+        assert_eq!(buf.swap_remove_first(4), Some(4));
+        assert_eq!(buf.len(), 5);
+        assert_eq!(buf[3], 0);
+        assert_eq!(buf, [1, 2, 3, 0, 5]);
+
+        let mut buf = fill();
+        assert_eq!(buf.swap_remove_last(1), Some(1));
+        assert_eq!(buf.len(), 5);
+        assert_eq!(buf[1], 5);
+        assert_eq!(buf, [0, 5, 2, 3, 4]);
+
+        let mut buf = fill();
+        assert_eq!(buf.swap_remove_last(5), Some(5));
+        assert_eq!(buf.swap_remove_first(0), Some(0));
+        assert_eq!(buf, [1, 2, 3, 4]);
+    }
 
-                # Examples
+    #[test]
+    fn test_insert_all_positions() {
+        for offset in 0..8 {
+            for len in 0..8 {
+                for index in 0..=len {
+                    let mut buf: ArrayDeque<usize, 8> = ArrayDeque::new();
+                    for _ in 0..offset {
+                        buf.push_last(0).unwrap();
+                        buf.pop_first();
+                    }
+                    for i in 0..len {
+                        buf.push_last(i).unwrap();
+                    }
+                    buf.insert(index, 100).unwrap();
+                    assert_eq!(buf.len(), len + 1);
+                    assert_eq!(buf.is_full(), len + 1 == 8);
+                    let expected = (0..index).chain([100]).chain(index..len);
+                    assert!(buf.iter().copied().eq(expected.clone()));
+                    let (a, b) = buf.as_slices();
+                    assert!(a.iter().chain(b).copied().eq(expected));
+                }
+            }
+        }
+    }
 
-                ```
-                use array_buf::", stringify!($struct_name), ";
+    #[test]
+    fn test_insert_full() {
+        let mut buf: ArrayDeque<String, 2> = ArrayDeque::new();
+        buf.insert(0, "b".to_string()).unwrap();
+        buf.insert(0, "a".to_string()).unwrap();
+        assert_eq!(buf.insert(1, "c".to_string()), Err("c".to_string()));
+
+        buf.set_overwrite(true);
+        assert_eq!(buf.insert(2, "c".to_string()), Err("c".to_string()));
+        assert_eq!(buf, ["a", "b"]);
+    }
 
-                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+    #[test]
+    #[should_panic(expected = "the len is 2 but the index is 2")]
+    fn test_swap_out_of_bounds() {
+        let mut buf: ArrayDequePlain<i32, 4> = ArrayDequePlain::new();
+        buf.push_last(1).unwrap();
+        buf.push_first(0).unwrap();
+        buf.swap(0, 1);
+        assert_eq!(buf, [1, 0]);
+        buf.swap(1, 2);
+    }
 
-                assert_eq!(buf.as_slices(), (&[][..], &[][..]));
+    #[test]
+    fn test_reverse_wrapped() {
+        for len in 0..=8 {
+            let mut buf: ArrayDeque<usize, 8> = ArrayDeque::new();
+            for _ in 0..5 {
+                buf.push_last(0).unwrap();
+                buf.pop_first();
+            }
+            for i in 0..len {
+                buf.push_last(i).unwrap();
+            }
+            buf.reverse();
+            let mut popped = Vec::new();
+            while let Some(v) = buf.pop_first() {
+                popped.push(v);
+            }
+            assert!(popped.into_iter().eq((0..len).rev()));
+        }
+    }
 
-                buf.push_last(1).unwrap();
-                buf.push_last(2).unwrap();
+    #[test]
+    fn test_rotate() {
+        for len in 0..=8 {
+            for n in 0..=10 {
+                let mut buf: ArrayDeque<usize, 8> = ArrayDeque::new();
+                for _ in 0..3 {
+                    buf.push_last(0).unwrap();
+                    buf.pop_first();
+                }
+                for i in 0..len {
+                    buf.push_last(i).unwrap();
+                }
+                let mut model: std::collections::VecDeque<usize> = (0..len).collect();
+
+                buf.rotate_left(n);
+                if len > 0 {
+                    model.rotate_left(n % len);
+                }
+                assert!(buf.iter().eq(model.iter()));
+
+                buf.rotate_right(n + 1);
+                if len > 0 {
+                    model.rotate_right((n + 1) % len);
+                }
+                assert!(buf.iter().eq(model.iter()));
+                assert_eq!(buf.is_full(), len == 8);
+            }
+        }
 
-                assert_eq!(buf.as_slices(), (&[1, 2][..], &[][..]));
+        let mut buf: ArrayDequePlain<i32, 4> = [1, 2, 3].iter().collect();
+        buf.rotate_left(1);
+        assert_eq!(buf.last(), Some(&1));
+    }
 
-                buf.push_first(-1).unwrap();
+    #[test]
+    fn test_push_overwrite_eviction_order() {
+        let mut buf: ArrayDeque<String, 4> = ArrayDeque::new();
+        for i in 0..4 {
+            assert_eq!(buf.push_last_overwrite(i.to_string()), None);
+        }
+        assert!(buf.is_full());
+
+        for i in 4..10 {
+            assert_eq!(
+                buf.push_last_overwrite(i.to_string()),
+                Some((i - 4).to_string())
+            );
+            assert_eq!(buf.len(), 4);
+        }
+        assert_eq!(buf, ["6", "7", "8", "9"]);
+
+        assert_eq!(
+            buf.push_first_overwrite("5".to_string()),
+            Some("9".to_string())
+        );
+        assert_eq!(
+            buf.push_first_overwrite("4".to_string()),
+            Some("8".to_string())
+        );
+        assert_eq!(buf, ["4", "5", "6", "7"]);
+
+        buf.pop_first();
+        assert_eq!(buf.push_first_overwrite("3".to_string()), None);
+        assert_eq!(buf, ["3", "5", "6", "7"]);
+    }
 
-                assert_eq!(buf.as_slices(), (&[-1][..], &[1, 2][..]));
-                ```
-            ")]
-            #[inline(always)]
-            pub fn as_slices(&self) -> (&[T], &[T]) {
-                self.0.as_slices()
+    #[test]
+    fn test_push_overwrite_cap_one() {
+        let mut buf: ArrayDequePlain<i32, 1> = ArrayDequePlain::new();
+        assert_eq!(buf.push_last_overwrite(1), None);
+        assert_eq!(buf.push_last_overwrite(2), Some(1));
+        assert_eq!(buf.push_first_overwrite(3), Some(2));
+        assert_eq!(buf, [3]);
+    }
+
+    #[test]
+    fn test_make_contiguous() {
+        for offset in 0..8 {
+            for len in 0..=8 {
+                let mut buf: ArrayDequePlain<usize, 8> = ArrayDequePlain::new();
+                for _ in 0..offset {
+                    buf.push_last(0).unwrap();
+                    buf.pop_first();
+                }
+                for i in 0..len {
+                    buf.push_last(i).unwrap();
+                }
+                let was_contiguous = buf.as_slices().1.is_empty();
+                let before = buf.as_slices().0.as_ptr();
+
+                assert!(buf.make_contiguous().iter().copied().eq(0..len));
+                assert_eq!(buf.as_slices().0.len(), len);
+                if was_contiguous && len > 0 {
+                    assert_eq!(buf.as_slices().0.as_ptr(), before);
+                }
             }
+        }
+    }
 
-            #[doc = concat!("
-                Returns a pair of slices which contain, in order, the contents of the
-                inner buffer.
+    #[test]
+    fn test_spare_capacity_all_offsets() {
+        for offset in 0..8 {
+            for len in 0..=8 {
+                let mut buf: ArrayDeque<String, 8> = ArrayDeque::new();
+                for _ in 0..offset {
+                    buf.push_last(String::new()).unwrap();
+                    buf.pop_first();
+                }
+                for i in 0..len {
+                    buf.push_last(i.to_string()).unwrap();
+                }
+                let (first, second) = buf.spare_capacity_mut();
+                assert_eq!(first.len() + second.len(), 8 - len);
+                for (i, slot) in first.iter_mut().chain(second).enumerate() {
+                    slot.write((len + i).to_string());
+                }
+                unsafe { buf.set_len_back(8 - len) };
 
-                # Examples
+                assert!(buf.is_full());
+                assert!(buf.iter().map(|v| v.parse::<usize>().unwrap()).eq(0..8));
+            }
+        }
+    }
 
-                ```
-                use array_buf::", stringify!($struct_name), ";
+    #[test]
+    fn test_push_full_returns_element() {
+        let mut buf: ArrayDeque<String, 2> = ArrayDeque::new();
+        buf.push_last("a".to_string()).unwrap();
+        buf.push_first("b".to_string()).unwrap();
+
+        let err = buf.push_last("c".to_string()).unwrap_err();
+        assert_eq!(err.to_string(), "insufficient capacity");
+        assert_eq!(err.into_inner(), "c");
+        assert_eq!(
+            buf.push_first("d".to_string()),
+            Err(CapacityError("d".to_string()))
+        );
+        assert_eq!(buf, ["b", "a"]);
+    }
 
-                let mut buf: ", stringify!($struct_name), "<_, 8> = ", stringify!($struct_name), "::new();
+    #[test]
+    fn test_fill_with_panic() {
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+
+        let mut buf: ArrayDeque<DropCounter, 8> = ArrayDeque::new();
+        buf.push_last(DropCounter(0)).unwrap();
+        buf.push_first(DropCounter(0)).unwrap();
+
+        let mut created = 0;
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            buf.fill_with(|| {
+                if created == 5 {
+                    panic!("fill_with");
+                }
+                created += 1;
+                DropCounter(created)
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(take_dropped(), [0, 0]);
+        assert_eq!(buf.len(), 5);
+
+        drop(buf);
+        assert_eq!(take_dropped(), [1, 2, 3, 4, 5]);
+    }
 
-                buf.push_last(1).unwrap();
-                buf.push_last(2).unwrap();
+    #[test]
+    fn test_clone_from_reuse() {
+        use std::{cell::Cell, rc::Rc};
+
+        #[derive(Debug, PartialEq)]
+        struct Tracked(String, Rc<Cell<usize>>, DropCounter);
+        impl Clone for Tracked {
+            fn clone(&self) -> Self {
+                Tracked(self.0.clone(), self.1.clone(), self.2.clone())
+            }
 
-                assert_eq!(buf.as_mut_slices(), (&mut [1, 2][..], &mut[][..]));
+            fn clone_from(&mut self, source: &Self) {
+                self.1.set(self.1.get() + 1);
+                self.0.clone_from(&source.0);
+            }
+        }
 
-                buf.push_first(-1);
+        let reused = Rc::new(Cell::new(0));
+        let new = |s: &str| Tracked(s.to_string(), reused.clone(), DropCounter(0));
+
+        let mut a: ArrayDeque<Tracked, 4> = ArrayDeque::new();
+        a.push_last(new("x")).unwrap();
+        a.push_first(new("y")).unwrap();
+        a.push_first(new("z")).unwrap();
+        let mut b: ArrayDeque<Tracked, 4> = ArrayDeque::new();
+        b.push_last(new("b")).unwrap();
+        b.push_first(new("a")).unwrap();
+
+        // shrinking: 2 reused, 1 dropped
+        a.clone_from(&b);
+        assert_eq!(a, b);
+        assert_eq!((reused.get(), dropped_count()), (2, 1));
+
+        // growing: 2 reused, 2 cloned
+        let mut c = b.clone();
+        c.push_last(new("c")).unwrap();
+        c.push_last(new("d")).unwrap();
+        a.clone_from(&c);
+        assert_eq!(a, c);
+        assert!(a.iter().map(|v| v.0.as_str()).eq(["a", "b", "c", "d"]));
+        assert_eq!((reused.get(), dropped_count()), (4, 1));
+
+        drop((a, b, c));
+        assert_eq!(dropped_count(), 11);
+    }
 
-                assert_eq!(buf.as_mut_slices(), (&mut[-1][..], &mut[1, 2][..]));
-                ```
+    #[test]
+    fn test_pop_if_untouched() {
+        let mut buf: ArrayDeque<String, 4> = ArrayDeque::new();
+        buf.push_last("b".to_string()).unwrap();
+        buf.push_first("a".to_string()).unwrap();
+        let before = buf.clone();
+
+        assert_eq!(buf.pop_first_if(|v| v == "b"), None);
+        assert_eq!(buf.pop_last_if(|v| v == "a"), None);
+        assert_eq!(buf, before);
+
+        assert_eq!(buf.pop_last_if(|v| v == "b").as_deref(), Some("b"));
+        assert_eq!(buf.pop_first_if(|v| v == "a").as_deref(), Some("a"));
+        assert_eq!(buf.pop_first_if(|_| true), None);
+        assert_eq!(buf.pop_last_if(|_| true), None);
+    }
 
-                ```
-                use array_buf::", stringify!($struct_name), ";
+    #[test]
+    fn test_pop_first_n() {
+        let fill = || {
+            let mut buf: ArrayDeque<String, 8> = ArrayDeque::new();
+            for i in 3..6 {
+                buf.push_last(i.to_string()).unwrap();
+            }
+            for i in (0..3).rev() {
+                buf.push_first(i.to_string()).unwrap();
+            }
+            buf
+        };
 
-                let mut buf: ", stringify!($struct_name), "<_, 4> = ", stringify!($struct_name), "::new();
+        // fewer than available
+        let mut buf = fill();
+        let mut out = vec![String::new(); 4];
+        assert_eq!(buf.pop_first_n(&mut out), 4);
+        assert_eq!(out, ["0", "1", "2", "3"]);
+        assert_eq!(buf, ["4", "5"]);
+
+        // exactly available
+        let mut buf = fill();
+        let mut out = vec![String::new(); 6];
+        assert_eq!(buf.pop_first_n(&mut out), 6);
+        assert_eq!(out, ["0", "1", "2", "3", "4", "5"]);
+        assert!(buf.is_empty());
+
+        // more than available
+        let mut buf = fill();
+        let mut out = vec!["x".to_string(); 8];
+        assert_eq!(buf.pop_first_n(&mut out), 6);
+        assert_eq!(out, ["0", "1", "2", "3", "4", "5", "x", "x"]);
+        assert!(buf.is_empty());
+        buf.push_last("6".to_string()).unwrap();
+        assert_eq!(buf, ["6"]);
+    }
 
-                assert_eq!(buf.as_mut_slices(), (&mut [][..], &mut[][..]));
+    #[test]
+    fn test_drain_early_drop() {
+        let mut buf: ArrayDeque<DropCounter, 8> = ArrayDeque::new();
+        for i in 3..6 {
+            buf.push_last(DropCounter(i)).unwrap();
+        }
+        for i in (0..3).rev() {
+            buf.push_first(DropCounter(i)).unwrap();
+        }
 
-                buf.push_last(1).unwrap();
-                buf.push_last(2).unwrap();
+        let mut drain = buf.drain(..);
+        assert_eq!(drain.len(), 6);
+        assert_eq!(drain.next().map(|v| v.0), Some(0));
+        assert_eq!(drain.next_back().map(|v| v.0), Some(5));
+        assert_eq!(take_dropped(), [0, 5]);
+        drop(drain);
+        assert_eq!(take_dropped(), [1, 2, 3, 4]);
+        assert!(buf.is_empty());
+
+        buf.push_last(DropCounter(6)).unwrap();
+        assert_eq!(buf.first().map(|v| v.0), Some(6));
+        mem::forget(buf.drain(..));
+        assert!(buf.is_empty());
+        drop(buf);
+        assert_eq!(dropped_count(), 0);
+    }
 
-                assert_eq!(buf.as_mut_slices(), (&mut [1, 2][..], &mut[][..]));
+    #[test]
+    fn test_drain_drop_panic() {
+        use std::panic::{AssertUnwindSafe, catch_unwind};
 
-                buf.push_first(-1).unwrap();
-                buf.push_first(-2).unwrap();
+        let mut buf: ArrayDeque<DropCounter, 4> = ArrayDeque::new();
+        for i in 0..4 {
+            buf.push_last(DropCounter(i)).unwrap();
+        }
+        panic_on_drop(1);
+        let result = catch_unwind(AssertUnwindSafe(|| drop(buf.drain(..))));
+        assert!(result.is_err());
+        assert_eq!(take_dropped(), [0, 1, 2, 3]);
+        assert!(buf.is_empty());
+    }
 
-                assert_eq!(buf.as_mut_slices(), (&mut[-2, -1][..], &mut[1, 2][..]));
-                ```
-            ")]
-            #[inline(always)]
-            pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
-                self.0.as_mut_slices()
+    #[test]
+    fn test_drain_range_all() {
+        for offset in 0..8 {
+            for len in 0..=8 {
+                for start in 0..=len {
+                    for end in start..=len {
+                        let mut buf: ArrayDeque<String, 8> = ArrayDeque::new();
+                        for _ in 0..offset {
+                            buf.push_last(String::new()).unwrap();
+                            buf.pop_first();
+                        }
+                        for i in 0..len {
+                            buf.push_last(i.to_string()).unwrap();
+                        }
+                        let mut drain = buf.drain(start..end);
+                        // leave a part not yielded
+                        if end > start {
+                            assert_eq!(drain.next(), Some(start.to_string()));
+                        }
+                        drop(drain);
+
+                        let expected = (0..len).filter(|i| *i < start || *i >= end);
+                        let expected: Vec<_> = expected.map(|i| i.to_string()).collect();
+                        assert_eq!(buf, expected.as_slice(), "{offset} {len} {start}..{end}");
+                        assert_eq!(buf.is_full(), expected.len() == 8);
+                        let (a, b) = buf.as_slices();
+                        assert!(a.iter().chain(b).eq(buf.iter()));
+                    }
+                }
             }
+        }
+    }
 
-            #[doc = concat!("
-                Make the buffer contiguous.
+    #[test]
+    fn test_drain_middle_wrapped() {
+        let mut buf: ArrayDeque<i32, 8> = ArrayDeque::new();
+        for v in 3..8 {
+            buf.push_last(v).unwrap();
+        }
+        for v in (0..3).rev() {
+            buf.push_first(v).unwrap();
+        }
+        assert!(!buf.as_slices().1.is_empty());
 
-                The linearization may be required when interacting with external
-                interfaces requiring contiguous slices.
+        assert!(buf.drain(2..=4).eq([2, 3, 4]));
+        assert_eq!(buf, [0, 1, 5, 6, 7]);
+        buf.push_last(8).unwrap();
+        buf.push_first(-1).unwrap();
+        assert_eq!(buf, [-1, 0, 1, 5, 6, 7, 8]);
+    }
 
-                # Examples
+    #[test]
+    #[should_panic(expected = "range end (is 3) should be <= len (is 2)")]
+    fn test_drain_out_of_bounds() {
+        let mut buf: ArrayDequePlain<i32, 4> = [1, 2].iter().collect();
+        buf.drain(1..3);
+    }
 
-                ```
-                use array_buf::", stringify!($struct_name), ";
+    #[test]
+    fn test_extract_if_early_drop() {
+        let mut buf: ArrayDeque<DropCounter, 8> = ArrayDeque::new();
+        for i in 4..8 {
+            buf.push_last(DropCounter(i)).unwrap();
+        }
+        for i in (0..4).rev() {
+            buf.push_first(DropCounter(i)).unwrap();
+        }
+        assert!(buf.is_full());
+
+        let mut iter = buf.extract_if(|v| v.0 % 3 == 0);
+        assert_eq!(iter.next().map(|v| v.0), Some(0));
+        assert_eq!(iter.next().map(|v| v.0), Some(3));
+        drop(iter);
+        assert_eq!(take_dropped(), [0, 3]);
+        assert!(buf.iter().map(|v| v.0).eq([1, 2, 4, 5, 6, 7]));
+
+        // exhausted, with the first element kept
+        assert!(buf.extract_if(|v| v.0 > 4).map(|v| v.0).eq([5, 6, 7]));
+        assert_eq!(take_dropped(), [5, 6, 7]);
+        assert!(buf.iter().map(|v| v.0).eq([1, 2, 4]));
+
+        // nothing extracted
+        assert_eq!(buf.extract_if(|_| false).count(), 0);
+        assert!(buf.iter().map(|v| v.0).eq([1, 2, 4]));
+
+        mem::forget(buf.extract_if(|_| true));
+        assert!(buf.is_empty());
+        drop(buf);
+        assert_eq!(dropped_count(), 0);
+    }
 
-                let mut buf: ", stringify!($struct_name), "<isize, 4> = ", stringify!($struct_name), "::new();
+    #[test]
+    fn test_extract_if_all_masks() {
+        for offset in 0..8 {
+            for mask in 0..256u32 {
+                let mut buf: ArrayDequePlain<u32, 8> = ArrayDequePlain::new();
+                for _ in 0..offset {
+                    buf.push_last(0).unwrap();
+                    buf.pop_first();
+                }
+                for i in 0..8 {
+                    buf.push_last(i).unwrap();
+                }
+                let extracted: Vec<u32> = buf.extract_if(|v| mask >> *v & 1 == 1).collect();
+                assert!(
+                    extracted
+                        .into_iter()
+                        .eq((0..8).filter(|v| mask >> v & 1 == 1))
+                );
+                assert!(
+                    buf.iter()
+                        .copied()
+                        .eq((0..8).filter(|v| mask >> v & 1 == 0))
+                );
+                assert_eq!(buf.is_full(), mask == 0);
+            }
+        }
+    }
 
-                buf.push_last(1).unwrap();
-                buf.push_first(-1).unwrap();
+    #[test]
+    fn test_dedup_wrapped() {
+        use std::rc::Rc;
 
-                assert!(!buf.is_contiguous());
+        let rc = Rc::new(());
+        let mut buf: ArrayDeque<(i32, Rc<()>), 8> = ArrayDeque::new();
+        for v in [3, 3, 3, 4, 5] {
+            buf.push_last((v, rc.clone())).unwrap();
+        }
+        for v in [2, 2, 1] {
+            buf.push_first((v, rc.clone())).unwrap();
+        }
+        assert!(!buf.as_slices().1.is_empty());
 
-                buf.linearize();
+        buf.dedup_by_key(|v| v.0);
+        assert!(buf.iter().map(|v| v.0).eq([1, 2, 3, 4, 5]));
+        assert_eq!(Rc::strong_count(&rc), 6);
 
-                assert!(buf.is_contiguous());
-                ```
-            ")]
-            #[inline(always)]
-            pub fn linearize(&mut self) {
-                self.0.linearize()
+        let mut buf: ArrayDequePlain<i32, 4> = ArrayDequePlain::new();
+        buf.dedup();
+        assert!(buf.is_empty());
+        buf.push_last(1).unwrap();
+        buf.dedup();
+        assert_eq!(buf, [1]);
+    }
+
+    #[test]
+    fn test_sort_wrapped() {
+        let mut seed = 7;
+        for offset in 0..8 {
+            for len in 0..=8 {
+                let mut buf: ArrayDeque<u64, 8> = ArrayDeque::new();
+                for _ in 0..offset {
+                    buf.push_last(0).unwrap();
+                    buf.pop_first();
+                }
+                for _ in 0..len {
+                    buf.push_last(xorshift(&mut seed) % 10).unwrap();
+                }
+                let mut expected: Vec<u64> = buf.iter().copied().collect();
+                expected.sort();
+
+                let mut unstable = buf.clone();
+                buf.sort();
+                unstable.sort_unstable();
+                assert_eq!(buf, expected.as_slice());
+                assert_eq!(unstable, expected.as_slice());
+                assert_eq!(buf.as_slices().0.len(), len);
+                assert_eq!(unstable.as_slices().0.len(), len);
             }
+        }
+    }
 
-            #[doc = concat!("
-                Make the buffer contiguous.
+    #[test]
+    fn test_is_sorted_across_wrap() {
+        let mut buf: ArrayDequePlain<i32, 8> = ArrayDequePlain::new();
+        for v in [5, 6, 7] {
+            buf.push_last(v).unwrap();
+        }
+        for v in [4, 2, 1] {
+            buf.push_first(v).unwrap();
+        }
+        let (a, b) = buf.as_slices();
+        assert!(a.is_sorted() && b.is_sorted());
+        assert!(buf.is_sorted());
+
+        // the only out of order pair is the last of the first slice and the first of the second
+        *buf.get_mut(2).unwrap() = 6;
+        let (a, b) = buf.as_slices();
+        assert_eq!((a.last(), b.first()), (Some(&6), Some(&5)));
+        assert!(a.is_sorted() && b.is_sorted());
+        assert!(!buf.is_sorted());
+        assert!(!buf.is_sorted_by(|a, b| a <= b));
+    }
 
-                The linearization may be required when interacting with external
-                interfaces requiring contiguous slices.
+    #[test]
+    fn test_swap_with_slice_wrapped() {
+        let mut buf: ArrayDequePlain<u8, 8> = ArrayDequePlain::new();
+        buf.extend_from_slice(b"xxxxx").unwrap();
+        for _ in 0..5 {
+            buf.pop_first();
+        }
+        buf.extend_from_slice(b"abcdef").unwrap();
+        assert_eq!(buf.as_slices(), (&b"abc"[..], &b"def"[..]));
 
-                # Examples
+        let mut staging = *b"123456";
+        buf.swap_with_slice(&mut staging);
+        assert_eq!(buf.as_slices(), (&b"123"[..], &b"456"[..]));
+        assert_eq!(&staging, b"abcdef");
+    }
 
-                ```
-                use array_buf::", stringify!($struct_name), ";
+    #[test]
+    #[should_panic(expected = "different lengths")]
+    fn test_swap_with_slice_len_mismatch() {
+        let mut buf: ArrayDequePlain<u8, 4> = ArrayDequePlain::new();
+        buf.push_last(1).unwrap();
+        buf.swap_with_slice(&mut [1, 2]);
+    }
 
-                let mut buf: ", stringify!($struct_name), "<isize, 2> = ", stringify!($struct_name), "::new();
+    #[test]
+    fn test_copy_from_slice() {
+        let mut buf: ArrayDequePlain<u8, 4> = ArrayDequePlain::new();
+        buf.push_last(1).unwrap();
+        buf.push_first(0).unwrap();
 
-                buf.push_first(-1).unwrap();
+        buf.copy_from_slice(b"abcd");
+        assert!(buf.is_full());
+        assert_eq!(buf.as_slices(), (&b"abcd"[..], &[][..]));
 
-                assert!(!buf.is_contiguous());
+        buf.copy_from_slice(b"");
+        assert!(buf.is_empty());
+        buf.push_first(b'z').unwrap();
+        assert_eq!(buf, *b"z");
+    }
 
-                buf.linearize_one();
+    #[test]
+    #[should_panic(expected = "source slice length (5) exceeds the capacity (4)")]
+    fn test_copy_from_slice_overflow() {
+        let mut buf: ArrayDequePlain<u8, 4> = ArrayDequePlain::new();
+        buf.copy_from_slice(b"abcde");
+    }
 
-                assert!(buf.is_contiguous());
-                ```
-            ")]
-            #[inline(always)]
-            pub fn linearize_one(&mut self) {
-                self.0.linearize_one()
-            }
+    #[test]
+    fn test_get_disjoint_mut_wrapped() {
+        let mut buf: ArrayDeque<String, 8> = ArrayDeque::new();
+        for v in ["c", "d", "e"] {
+            buf.push_last(v.to_string()).unwrap();
         }
-    };
-}
+        for v in ["b", "a"] {
+            buf.push_first(v.to_string()).unwrap();
+        }
+        assert!(!buf.as_slices().1.is_empty());
+
+        // the first and the second slices
+        let (a, e) = buf.get_disjoint_mut(0, 4).unwrap();
+        mem::swap(a, e);
+        assert_eq!(buf, ["e", "b", "c", "d", "a"]);
+
+        // both in the second slice
+        let (c, d) = buf.get_disjoint_mut(2, 3).unwrap();
+        c.push_str(d);
+        assert_eq!(buf[2], "cd");
+        assert!(buf.get_disjoint_mut(4, 5).is_none());
+    }
 
-/// A fixed capacity deque for plain data (`Copy`, no `Drop`). Capacity must be in the power of two.
-///
-/// Can be stored directly on the stack.
-///
-/// The "default" usage of this as a queue is to use `push_last` to add to
-/// the queue, and `pop_first` to consume from the queue.
-#[derive(Copy, Clone, Debug, Default)]
-#[repr(transparent)]
-pub struct ArrayDequePlain<T: Copy, const CAP: usize>(ArrayDequeBase<T, CAP>);
+    #[test]
+    fn test_fmt_write_wrapped() {
+        use core::fmt::Write;
 
-reimpl_common_methods!(ArrayDequePlain<Copy>);
+        let mut buf: ArrayDeque<u8, 16> = ArrayDeque::new();
+        write!(buf, "{:>10}", "").unwrap();
+        while buf.pop_first().is_some() {}
+        write!(buf, "value={}", -1234).unwrap();
+        assert!(!buf.as_slices().1.is_empty());
 
-impl<T: Copy, const CAP: usize> ArrayDequePlain<T, CAP> {
-    /// Clears the buffer by resetting the indexes.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use array_buf::ArrayDequePlain;
-    ///
-    /// let mut buf: ArrayDequePlain<_, 4> = ArrayDequePlain::new();
-    ///
-    /// buf.push_last(1).unwrap();
-    /// buf.push_first(-1).unwrap();
-    /// buf.clear();
-    ///
-    /// assert!(buf.is_empty());
-    /// ```
-    #[inline]
-    pub fn clear(&mut self) {
-        self.0.clear();
+        buf.linearize();
+        let s = core::str::from_utf8(unsafe { buf.as_slice() }).unwrap();
+        assert_eq!(s, "value=-1234");
+
+        assert_eq!(write!(buf, "{}", 123456), Err(fmt::Error));
+        assert_eq!(buf.len(), 11);
     }
-}
 
-/// A fixed capacity deque. Capacity must be in the power of two.
-/// If you have plain data, better use `ArrayDequePlain`.
-///
-/// Can be stored directly on the stack.
-///
-/// The "default" usage of this as a queue is to use `push_last` to add to
-/// the queue, and `pop_first` to consume from the queue.
-#[derive(Debug, Default)]
-#[repr(transparent)]
-pub struct ArrayDeque<T, const CAP: usize>(ArrayDequeBase<T, CAP>);
+    #[test]
+    fn test_zst() {
+        let mut buf: ArrayDeque<(), 4> = ArrayDeque::new();
+        assert_eq!(buf.pop_first(), None);
+        for len in 1..=4 {
+            buf.push_last(()).unwrap();
+            assert_eq!(buf.len(), len);
+        }
+        assert!(buf.is_full());
+        assert!(buf.push_last(()).is_err());
+        assert!(buf.push_first(()).is_err());
+        assert_eq!(buf.iter().count(), 4);
+        let (a, b) = buf.as_slices();
+        assert_eq!(a.len() + b.len(), 4);
+
+        assert_eq!(buf.pop_first(), Some(()));
+        assert_eq!(buf.pop_last(), Some(()));
+        assert_eq!(buf.len(), 2);
+        assert!(!buf.is_full());
+
+        // wrap around
+        buf.push_first(()).unwrap();
+        buf.push_first(()).unwrap();
+        assert!(buf.is_full());
+        buf.linearize();
+        assert_eq!(buf.len(), 4);
+        assert_eq!(buf.drain(1..3).count(), 2);
+        assert_eq!(buf.len(), 2);
+        buf.clear();
+        assert!(buf.is_empty());
+
+        let mut plain: ArrayDequePlain<(), 3> = ArrayDequePlain::new();
+        plain.push_last(()).unwrap();
+        plain.push_first(()).unwrap();
+        assert_eq!(plain.len(), 2);
+        assert_eq!(plain.take_all().count(), 2);
+    }
 
-impl<T: Clone, const CAP: usize> Clone for ArrayDeque<T, CAP> {
-    #[inline(always)]
-    fn clone(&self) -> Self {
-        Self(self.0.clone())
+    #[test]
+    fn test_zst_drop() {
+        assert_eq!(size_of::<DropCounter<()>>(), 0);
+        let mut buf: ArrayDeque<DropCounter<()>, 4> = ArrayDeque::new();
+        for _ in 0..3 {
+            buf.push_last(DropCounter(())).unwrap();
+            buf.pop_first().unwrap();
+        }
+        assert_eq!(dropped_count(), 3);
+        for _ in 0..4 {
+            buf.push_first(DropCounter(())).unwrap();
+        }
+        assert!(buf.push_last(DropCounter(())).is_err());
+        assert_eq!(dropped_count(), 4);
+        buf.drain(1..);
+        assert_eq!(dropped_count(), 7);
+        assert_eq!(buf.len(), 1);
+        drop(buf);
+        assert_eq!(dropped_count(), 8);
     }
-}
 
-reimpl_common_methods!(ArrayDeque);
+    #[test]
+    fn test_raw_parts_round_trip() {
+        use std::rc::Rc;
+
+        let rc = Rc::new(());
+        let mut buf: ArrayDeque<Rc<()>, 4> = ArrayDeque::new();
+        buf.set_overwrite(true);
+        for _ in 0..5 {
+            buf.push_last(rc.clone()).unwrap();
+        }
+        assert_eq!(Rc::strong_count(&rc), 5);
+
+        let (arr, start, end, full) = buf.into_raw_parts();
+        assert!(full && start == end);
+        // the array of `MaybeUninit` doesn't drop
+        assert_eq!(Rc::strong_count(&rc), 5);
+
+        let mut buf = unsafe { ArrayDeque::from_raw_parts(arr, start, end, full) };
+        assert!(buf.is_full());
+        assert!(!buf.is_overwrite());
+        assert!(buf.push_last(rc.clone()).is_err());
+        assert_eq!(buf.len(), 4);
+        drop(buf);
+        assert_eq!(Rc::strong_count(&rc), 1);
+
+        let buf: ArrayDequePlain<i32, 4> = ArrayDequePlain::new();
+        let (arr, start, end, full) = buf.into_raw_parts();
+        let buf = unsafe { ArrayDequePlain::from_raw_parts(arr, start, end, full) };
+        assert!(buf.is_empty());
+    }
 
-impl<T, const CAP: usize> ArrayDeque<T, CAP> {
-    #[inline]
-    fn drop_arr_vals(&mut self) {
-        let (mem_right, mem_left) = self.as_mut_slices();
-        // iterating in order of incrementing mem address
-        for v in mem_left.iter_mut().chain(mem_right) {
-            unsafe { ptr::drop_in_place(v) };
+    #[test]
+    fn test_map_panic() {
+        use std::{panic, rc::Rc};
+
+        let rc = Rc::new(());
+        let mut buf: ArrayDeque<Rc<()>, 8> = ArrayDeque::new();
+        for _ in 0..3 {
+            buf.push_last(rc.clone()).unwrap();
         }
+        for _ in 0..3 {
+            buf.push_first(rc.clone()).unwrap();
+        }
+        let mut calls = 0;
+        let res = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            buf.map(|v| {
+                calls += 1;
+                if calls == 4 {
+                    panic!("map");
+                }
+                (v, calls)
+            })
+        }));
+        assert!(res.is_err());
+        assert_eq!(Rc::strong_count(&rc), 1);
+
+        let mut buf: ArrayDeque<String, 4> = ArrayDeque::new();
+        buf.push_last("b".to_string()).unwrap();
+        buf.push_first("a".to_string()).unwrap();
+        let mapped = buf.map(|s| s.len());
+        assert_eq!(mapped.len(), 2);
+        assert!(mapped.iter().eq(&[1, 1]));
     }
 
-    /// Clears the buffer by dropping and resetting the indexes.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use array_buf::ArrayDeque;
-    ///
-    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
-    ///
-    /// buf.push_last(1).unwrap();
-    /// buf.push_first(-1).unwrap();
-    /// buf.clear();
-    ///
-    /// assert!(buf.is_empty());
-    /// ```
-    #[inline]
-    pub fn clear(&mut self) {
-        self.drop_arr_vals();
-        self.0.clear();
+    #[test]
+    fn test_plain_conversion() {
+        let mut buf: ArrayDeque<i32, 4> = ArrayDeque::new();
+        buf.set_overwrite(true);
+        for v in 0..6 {
+            buf.push_last(v).unwrap();
+        }
+        let slices = (buf.as_slices().0.to_vec(), buf.as_slices().1.to_vec());
+        assert_eq!(slices, (vec![2, 3], vec![4, 5]));
+
+        let plain: ArrayDequePlain<i32, 4> = buf.into_plain();
+        assert!(plain.is_full());
+        assert!(plain.is_overwrite());
+        assert_eq!(plain.as_slices(), (&slices.0[..], &slices.1[..]));
+
+        let mut buf = ArrayDeque::from_plain(plain);
+        assert_eq!(buf.as_slices(), (&slices.0[..], &slices.1[..]));
+        buf.pop_first().unwrap();
+        let plain = ArrayDequePlain::from(buf);
+        assert!(plain.iter().eq(&[3, 4, 5]));
+        assert!(ArrayDeque::from(plain).iter().eq(&[3, 4, 5]));
     }
-}
 
-impl<T, const CAP: usize> Drop for ArrayDeque<T, CAP> {
-    #[inline(always)]
-    fn drop(&mut self) {
-        self.drop_arr_vals();
+    #[test]
+    fn test_spare_capacity_scatter() {
+        let mut buf: ArrayDeque<String, 8> = ArrayDeque::new();
+        for _ in 0..5 {
+            buf.push_last(String::new()).unwrap();
+            buf.pop_first().unwrap();
+        }
+        buf.push_last("a".to_string()).unwrap();
+
+        let (first, second) = buf.as_uninit_slices_mut();
+        assert_eq!((first.len(), second.len()), (2, 5));
+        // written out of order, across the wrap
+        second[1].write("e".to_string());
+        first[1].write("c".to_string());
+        second[0].write("d".to_string());
+        first[0].write("b".to_string());
+        unsafe { buf.set_len_back(4) };
+
+        assert!(buf.iter().eq(["a", "b", "c", "d", "e"]));
+        assert!(!buf.is_contiguous());
+        let (first, second) = buf.spare_capacity_mut();
+        assert_eq!((first.len(), second.len()), (3, 0));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_range_wrapped() {
+        let mut buf: ArrayDeque<usize, 8> = ArrayDeque::new();
+        for _ in 0..5 {
+            buf.push_last(0).unwrap();
+            buf.pop_first().unwrap();
+        }
+        for i in 0..7 {
+            buf.push_last(i).unwrap();
+        }
+        assert_eq!(buf.as_slices().0.len(), 3);
+
+        for start in 0..=7 {
+            for end in start..=7 {
+                assert!(buf.range(start..end).copied().eq(start..end));
+                assert!(buf.range(start..end).rev().copied().eq((start..end).rev()));
+                assert_eq!(buf.range(start..end).len(), end - start);
+            }
+        }
+        // entirely in the wrapped tail
+        assert_eq!(buf.range(4..6).size_hint(), (2, Some(2)));
+        for v in buf.range_mut(4..6) {
+            *v += 10;
+        }
+        assert!(buf.iter().copied().eq([0, 1, 2, 3, 14, 15, 6]));
+    }
 
     #[test]
-    fn test_copy() {
-        let mut a = ArrayDequePlain::<i32, 8>::new();
-        a.push_last(1).unwrap();
-        a.push_last(2).unwrap();
+    #[should_panic(expected = "range end (is 3) should be <= len (is 2)")]
+    fn test_range_out_of_bounds() {
+        let mut buf: ArrayDequePlain<i32, 4> = ArrayDequePlain::new();
+        buf.push_last(1).unwrap();
+        buf.push_last(2).unwrap();
+        buf.range(1..3);
+    }
 
-        // contiguous
-        let mut b = a;
-        assert_eq!(a.pop_first(), Some(1));
-        assert_eq!(a.len(), 1);
-        assert_eq!(b.len(), 2);
-        assert_eq!(b.pop_first(), Some(1));
+    #[test]
+    fn test_into_vec_wrapped() {
+        use std::rc::Rc;
+
+        let rc = Rc::new(());
+        let mut buf: ArrayDeque<(usize, Rc<()>), 4> = ArrayDeque::new();
+        buf.push_last((2, rc.clone())).unwrap();
+        buf.push_last((3, rc.clone())).unwrap();
+        buf.push_first((1, rc.clone())).unwrap();
+        assert!(!buf.is_contiguous());
+
+        let cloned = buf.to_vec();
+        assert_eq!(Rc::strong_count(&rc), 7);
+        let vec = buf.into_vec();
+        assert_eq!(Rc::strong_count(&rc), 7);
+        assert!(vec.iter().map(|v| v.0).eq([1, 2, 3]));
+        assert!(cloned.iter().map(|v| v.0).eq([1, 2, 3]));
+        drop(vec);
+        drop(cloned);
+        assert_eq!(Rc::strong_count(&rc), 1);
+
+        let mut plain: ArrayDequePlain<u8, 2> = ArrayDequePlain::new();
+        plain.push_last(2).unwrap();
+        plain.push_first(1).unwrap();
+        assert_eq!(plain.into_vec(), plain.to_vec());
+    }
 
-        // not contiguous
-        a.push_first(-1).unwrap();
-        a.push_first(-2).unwrap();
-        let mut c = a;
-        assert_eq!(a.pop_first(), Some(-2));
-        assert_eq!(a.len(), 2);
-        assert_eq!(c.len(), 3);
-        assert_eq!(c.pop_first(), Some(-2));
-        assert_eq!(b.len(), 1);
+    #[test]
+    fn test_rotate_one() {
+        for len in 0..=5 {
+            let mut buf: ArrayDeque<usize, 5> = ArrayDeque::new();
+            let mut model: std::collections::VecDeque<usize> = (0..len).collect();
+            buf.extend(0..len);
+            for i in 0..12 {
+                if i % 3 == 0 {
+                    buf.rotate_right_one();
+                    model.rotate_right(len.min(1));
+                } else {
+                    buf.rotate_left_one();
+                    model.rotate_left(len.min(1));
+                }
+                assert!(buf.iter().eq(&model), "len {len} step {i}");
+                assert_eq!(buf.is_full(), len == 5);
+            }
+        }
     }
 
     #[test]
-    fn test_clone() {
-        let mut a = ArrayDeque::<String, 8>::new();
-        a.push_last("1".to_owned()).unwrap();
+    fn test_as_ptr_full_rotated() {
+        let mut buf: ArrayDeque<i32, 4> = ArrayDeque::new();
+        for v in 0..4 {
+            buf.push_last(v).unwrap();
+        }
+        buf.pop_first().unwrap();
+        buf.push_last(4).unwrap();
+        assert!(buf.is_full());
+        assert_eq!(buf.first(), Some(&1));
+        // full, starting at the slot 1, so not contiguous
+        #[cfg(debug_assertions)]
+        assert!(std::panic::catch_unwind(|| buf.as_ptr()).is_err());
+
+        buf.linearize();
+        let slice = unsafe { slice::from_raw_parts(buf.as_ptr(), buf.len()) };
+        assert_eq!(slice, [1, 2, 3, 4]);
+
+        // contiguous, starting at the slot 1
+        let mut buf: ArrayDeque<i32, 4> = ArrayDeque::new();
+        buf.push_last(0).unwrap();
+        buf.pop_first().unwrap();
+        for v in 1..4 {
+            buf.push_last(v).unwrap();
+        }
+        assert_eq!(buf.as_slices(), (&[1, 2, 3][..], &[][..]));
+        assert_eq!(unsafe { *buf.as_ptr() }, 1);
+        unsafe { *buf.as_mut_ptr() = 10 };
+        assert_eq!(buf.first(), Some(&10));
+    }
 
-        // contiguous
-        let mut b = a.clone();
-        a.first_mut().unwrap().push('x');
-        assert_eq!(a.pop_first(), Some("1x".to_owned()));
-        assert_eq!(b.first().map(|s| s.as_str()), Some("1"));
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn test_size_odd_cap() {
+        // the flags fit into the padding
+        assert_eq!(size_of::<ArrayDeque<u8, 6>>(), 24);
+        // the overwrite flag doesn't, see `set_overwrite()`
+        assert_eq!(size_of::<ArrayDeque<u8, 7>>(), 32);
+        assert_eq!(size_of::<ArrayDeque<[u8; 7], 1>>(), 32);
+        assert_eq!(size_of::<ArrayDequePlain<u64, 3>>(), 48);
+    }
 
-        // not contiguous
-        b.push_first("-1".to_owned()).unwrap();
-        let c = b.clone();
-        b.first_mut().unwrap().push('x');
-        assert_eq!(b.pop_first(), Some("-1x".to_owned()));
-        assert_eq!(c.first().map(|s| s.as_str()), Some("-1"));
+    #[test]
+    fn test_logical_to_physical_non_power_of_two() {
+        let mut buf: ArrayDequePlain<u8, 10> = ArrayDequePlain::new();
+        for _ in 0..9 {
+            buf.push_last(0).unwrap();
+            buf.pop_first().unwrap();
+        }
+        buf.push_last(1).unwrap();
+        buf.push_last(2).unwrap();
+
+        assert_eq!(buf.logical_to_physical(0), 9);
+        assert_eq!(buf.logical_to_physical(1), 0);
+        assert_eq!(buf.logical_to_physical(10), 9);
+        assert_eq!(buf.logical_to_physical(25), 4);
+        for index in [usize::MAX, usize::MAX - 1, usize::MAX / 2] {
+            assert!(buf.logical_to_physical(index) < 10);
+            assert_eq!(buf.logical_to_physical(index), (9 + index % 10) % 10);
+        }
     }
 
     #[test]
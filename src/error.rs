@@ -0,0 +1,54 @@
+use ::core::fmt::{self, Debug, Display};
+
+/// Error returned when there is not enough capacity. Contains the rejected element.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError<T>(pub T);
+
+impl<T> CapacityError<T> {
+    /// Returns the rejected element.
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Debug for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CapacityError(..)")
+    }
+}
+
+impl<T> Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("insufficient capacity")
+    }
+}
+
+impl<T> ::core::error::Error for CapacityError<T> {}
+
+/// Error returned by a push with conversion (e.g. `try_push_last()`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PushError<E> {
+    /// The value can't be converted into the element type.
+    Conversion(E),
+    /// The array is full.
+    Full,
+}
+
+impl<E: Display> Display for PushError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Conversion(e) => write!(f, "conversion failed: {e}"),
+            Self::Full => f.write_str("array is full"),
+        }
+    }
+}
+
+impl<E: ::core::error::Error + 'static> ::core::error::Error for PushError<E> {
+    fn source(&self) -> Option<&(dyn ::core::error::Error + 'static)> {
+        match self {
+            Self::Conversion(e) => Some(e),
+            Self::Full => None,
+        }
+    }
+}
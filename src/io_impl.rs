@@ -0,0 +1,145 @@
+use crate::{ArrayDeque, ArrayDequePlain};
+use ::std::{io, ptr};
+
+macro_rules! impl_io {
+    ($struct_name:ident) => {
+        /// Appends as many bytes as fit to the back, returning their number.
+        #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+        impl<const CAP: usize> io::Write for $struct_name<u8, CAP> {
+            #[inline]
+            fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+                let (first, second) = self.spare_capacity_mut();
+                let first_len = data.len().min(first.len());
+                let second_len = (data.len() - first_len).min(second.len());
+                unsafe {
+                    ptr::copy_nonoverlapping(data.as_ptr(), first.as_mut_ptr().cast(), first_len);
+                    ptr::copy_nonoverlapping(
+                        data.as_ptr().add(first_len),
+                        second.as_mut_ptr().cast(),
+                        second_len,
+                    );
+                    self.set_len_back(first_len + second_len);
+                }
+                Ok(first_len + second_len)
+            }
+
+            #[inline(always)]
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        /// Moves bytes from the front into `out`, returning their number.
+        #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+        impl<const CAP: usize> io::Read for $struct_name<u8, CAP> {
+            #[inline]
+            fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+                let (first, second) = self.as_slices();
+                let first_len = out.len().min(first.len());
+                let second_len = (out.len() - first_len).min(second.len());
+                out[..first_len].copy_from_slice(&first[..first_len]);
+                out[first_len..first_len + second_len].copy_from_slice(&second[..second_len]);
+                self.drain(..first_len + second_len);
+                Ok(first_len + second_len)
+            }
+        }
+
+        /// `fill_buf()` returns only the first contiguous run of bytes (`as_slices().0`),
+        /// so the wrapped bytes are returned by the next call, after `consume()`.
+        #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+        impl<const CAP: usize> io::BufRead for $struct_name<u8, CAP> {
+            #[inline]
+            fn fill_buf(&mut self) -> io::Result<&[u8]> {
+                Ok(self.as_slices().0)
+            }
+
+            #[inline]
+            fn consume(&mut self, amt: usize) {
+                self.drain(..amt.min(self.len()));
+            }
+        }
+    };
+}
+
+impl_io!(ArrayDeque);
+impl_io!(ArrayDequePlain);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::std::io::{BufRead, Read, Write};
+
+    #[test]
+    fn test_write_partial() {
+        let mut buf: ArrayDeque<u8, 8> = ArrayDeque::new();
+        buf.write_all(b"abcde").unwrap();
+        for _ in 0..3 {
+            buf.pop_first();
+        }
+
+        // wraps, then fills in the middle of the slice
+        assert_eq!(buf.write(b"fghijklm").unwrap(), 6);
+        assert_eq!(buf.as_slices(), (&b"defgh"[..], &b"ijk"[..]));
+        assert_eq!(buf.write(b"z").unwrap(), 0);
+        assert!(buf.write_all(b"z").is_err());
+        buf.flush().unwrap();
+
+        let mut plain: ArrayDequePlain<u8, 4> = ArrayDequePlain::new();
+        assert_eq!(io::copy(&mut &b"12"[..], &mut plain).unwrap(), 2);
+        assert_eq!(plain, *b"12");
+    }
+
+    #[test]
+    fn test_read_wrapped() {
+        let mut buf: ArrayDequePlain<u8, 8> = ArrayDequePlain::new();
+        buf.write_all(b"xxxxxx").unwrap();
+        let mut out = [0; 5];
+        buf.read_exact(&mut out).unwrap();
+        buf.write_all(b"abcde").unwrap();
+        buf.read_exact(&mut out[..1]).unwrap();
+        assert_eq!(buf.as_slices(), (&b"ab"[..], &b"cde"[..]));
+
+        let mut out = [0; 3];
+        assert_eq!(buf.read(&mut out).unwrap(), 3);
+        assert_eq!(&out, b"abc");
+
+        // larger than the contents
+        let mut out = [0; 8];
+        assert_eq!(buf.read(&mut out).unwrap(), 2);
+        assert_eq!(&out[..2], b"de");
+        assert!(buf.is_empty());
+        assert_eq!(buf.read(&mut out).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_pipe() {
+        let mut pipe: ArrayDeque<u8, 4> = ArrayDeque::new();
+        let mut received = Vec::new();
+        let mut data = &b"hello, world"[..];
+        while !data.is_empty() {
+            let n = pipe.write(data).unwrap();
+            data = &data[n..];
+            pipe.read_to_end(&mut received).unwrap();
+        }
+        assert_eq!(received, b"hello, world");
+    }
+
+    #[test]
+    fn test_read_until_wrapped() {
+        let mut buf: ArrayDeque<u8, 16> = ArrayDeque::new();
+        buf.write_all(&[0; 12]).unwrap();
+        buf.consume(11);
+        buf.write_all(b"one\ntwo\nend").unwrap();
+        buf.consume(1);
+        assert_eq!(buf.fill_buf().unwrap(), b"one\n");
+
+        let mut line = Vec::new();
+        buf.read_until(b'\n', &mut line).unwrap();
+        assert_eq!(line, b"one\n");
+
+        let mut lines = buf.lines();
+        assert_eq!(lines.next().unwrap().unwrap(), "two");
+        assert_eq!(lines.next().unwrap().unwrap(), "end");
+        assert!(lines.next().is_none());
+    }
+}
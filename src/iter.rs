@@ -0,0 +1,346 @@
+use crate::deque::ArrayDequeBase;
+use ::core::{fmt::Debug, iter::FusedIterator, mem, ops::Range, ptr, slice};
+
+/// An iterator over the elements of a deque, in order.
+#[derive(Clone)]
+pub struct Iter<'a, T> {
+    first: slice::Iter<'a, T>,
+    second: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    #[inline(always)]
+    pub(crate) fn new((first, second): (&'a [T], &'a [T])) -> Self {
+        Self {
+            first: first.iter(),
+            second: second.iter(),
+        }
+    }
+}
+
+impl<T: Debug> Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.debug_tuple("Iter")
+            .field(&self.first.as_slice())
+            .field(&self.second.as_slice())
+            .finish()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        match self.first.next() {
+            Some(v) => Some(v),
+            None => {
+                mem::swap(&mut self.first, &mut self.second);
+                self.first.next()
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn fold<B, F: FnMut(B, Self::Item) -> B>(self, init: B, f: F) -> B {
+        self.first.chain(self.second).fold(init, f)
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.second.next_back() {
+            Some(v) => Some(v),
+            None => {
+                mem::swap(&mut self.first, &mut self.second);
+                self.second.next_back()
+            }
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.first.len() + self.second.len()
+    }
+}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// A mutable iterator over the elements of a deque, in order.
+pub struct IterMut<'a, T> {
+    first: slice::IterMut<'a, T>,
+    second: slice::IterMut<'a, T>,
+}
+
+impl<'a, T> IterMut<'a, T> {
+    #[inline(always)]
+    pub(crate) fn new((first, second): (&'a mut [T], &'a mut [T])) -> Self {
+        Self {
+            first: first.iter_mut(),
+            second: second.iter_mut(),
+        }
+    }
+}
+
+impl<T: Debug> Debug for IterMut<'_, T> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.debug_tuple("IterMut")
+            .field(&self.first.as_slice())
+            .field(&self.second.as_slice())
+            .finish()
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a mut T> {
+        match self.first.next() {
+            Some(v) => Some(v),
+            None => {
+                mem::swap(&mut self.first, &mut self.second);
+                self.first.next()
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn fold<B, F: FnMut(B, Self::Item) -> B>(self, init: B, f: F) -> B {
+        self.first.chain(self.second).fold(init, f)
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.second.next_back() {
+            Some(v) => Some(v),
+            None => {
+                mem::swap(&mut self.first, &mut self.second);
+                self.second.next_back()
+            }
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.first.len() + self.second.len()
+    }
+}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// An owning iterator over the elements of a deque, in order.
+pub struct IntoIter<T, const CAP: usize>(pub(crate) ArrayDequeBase<T, CAP>);
+
+impl<T: Debug, const CAP: usize> Debug for IntoIter<T, CAP> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.debug_tuple("IntoIter")
+            .field(&self.0.as_slices())
+            .finish()
+    }
+}
+
+impl<T, const CAP: usize> Iterator for IntoIter<T, CAP> {
+    type Item = T;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<T> {
+        self.0.pop_first()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.len();
+        (len, Some(len))
+    }
+}
+
+impl<T, const CAP: usize> DoubleEndedIterator for IntoIter<T, CAP> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<T> {
+        self.0.pop_last()
+    }
+}
+
+impl<T, const CAP: usize> ExactSizeIterator for IntoIter<T, CAP> {}
+
+impl<T, const CAP: usize> FusedIterator for IntoIter<T, CAP> {}
+
+impl<T, const CAP: usize> Drop for IntoIter<T, CAP> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.0.drop_arr_vals();
+    }
+}
+
+/// A draining iterator over a range of elements of a deque, in order.
+///
+/// The elements which weren't yielded are dropped, and the remaining elements
+/// are joined together with the iterator.
+pub struct Drain<'a, T, const CAP: usize> {
+    /// Contains only the elements before the drained range.
+    deque: &'a mut ArrayDequeBase<T, CAP>,
+    /// Logical indexes (from the start) of the elements not yielded yet.
+    range: Range<usize>,
+    /// Number of the drained slots between the deque and the tail.
+    drained: usize,
+    /// Number of the elements after the drained range.
+    tail_len: usize,
+}
+
+impl<'a, T, const CAP: usize> Drain<'a, T, CAP> {
+    /// The `deque` must contain only the elements before the initialized `range`,
+    /// which is followed by `tail_len` initialized elements.
+    #[inline(always)]
+    pub(crate) fn new(
+        deque: &'a mut ArrayDequeBase<T, CAP>,
+        range: Range<usize>,
+        tail_len: usize,
+    ) -> Self {
+        Self {
+            deque,
+            drained: range.len(),
+            range,
+            tail_len,
+        }
+    }
+
+    /// Drops the elements which weren't yielded.
+    #[inline]
+    fn drop_rest(&mut self) {
+        for i in self.range.by_ref() {
+            unsafe { ptr::drop_in_place(self.deque.logical_ptr(i)) };
+        }
+    }
+}
+
+impl<T, const CAP: usize> Iterator for Drain<'_, T, CAP> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        let i = self.range.next()?;
+        Some(unsafe { self.deque.read_logical(i) })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<T, const CAP: usize> DoubleEndedIterator for Drain<'_, T, CAP> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        let i = self.range.next_back()?;
+        Some(unsafe { self.deque.read_logical(i) })
+    }
+}
+
+impl<T, const CAP: usize> ExactSizeIterator for Drain<'_, T, CAP> {}
+
+impl<T, const CAP: usize> FusedIterator for Drain<'_, T, CAP> {}
+
+impl<T, const CAP: usize> Drop for Drain<'_, T, CAP> {
+    fn drop(&mut self) {
+        /// Continues dropping the rest and joins the tail if a drop panics.
+        struct DropGuard<'r, 'a, T, const CAP: usize>(&'r mut Drain<'a, T, CAP>);
+
+        impl<T, const CAP: usize> Drop for DropGuard<'_, '_, T, CAP> {
+            fn drop(&mut self) {
+                self.0.drop_rest();
+                unsafe { self.0.deque.join_tail(self.0.drained, self.0.tail_len) };
+            }
+        }
+
+        let guard = DropGuard(self);
+        guard.0.drop_rest();
+        mem::forget(guard);
+        unsafe { self.deque.join_tail(self.drained, self.tail_len) };
+    }
+}
+
+/// An iterator which removes the elements matching the predicate, see `extract_if()`.
+///
+/// The deque is empty until the iterator is dropped, then the rest of the elements
+/// are moved to close the gaps.
+pub struct ExtractIf<'a, T, const CAP: usize, F> {
+    deque: &'a mut ArrayDequeBase<T, CAP>,
+    pred: F,
+    /// Logical index of the next element to check.
+    idx: usize,
+    /// Number of the extracted elements.
+    del: usize,
+    old_len: usize,
+}
+
+impl<'a, T, const CAP: usize, F> ExtractIf<'a, T, CAP, F> {
+    #[inline]
+    pub(crate) fn new(deque: &'a mut ArrayDequeBase<T, CAP>, pred: F) -> Self {
+        let old_len = deque.len();
+        // the deque is emptied, so leaking the iterator doesn't expose moved values
+        unsafe { deque.set_len(0) };
+        Self {
+            deque,
+            pred,
+            idx: 0,
+            del: 0,
+            old_len,
+        }
+    }
+}
+
+impl<T, const CAP: usize, F: FnMut(&mut T) -> bool> Iterator for ExtractIf<'_, T, CAP, F> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        while self.idx < self.old_len {
+            let i = self.idx;
+            // if the predicate panics, the element is kept
+            let extract = (self.pred)(unsafe { &mut *self.deque.logical_ptr(i) });
+            self.idx += 1;
+            if extract {
+                self.del += 1;
+                return Some(unsafe { self.deque.read_logical(i) });
+            } else if self.del > 0 {
+                unsafe { self.deque.copy_logical(i, i - self.del) };
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.old_len - self.idx))
+    }
+}
+
+impl<T, const CAP: usize, F> Drop for ExtractIf<'_, T, CAP, F> {
+    fn drop(&mut self) {
+        if self.del > 0 {
+            for i in self.idx..self.old_len {
+                unsafe { self.deque.copy_logical(i, i - self.del) };
+            }
+        }
+        unsafe { self.deque.set_len(self.old_len - self.del) };
+    }
+}
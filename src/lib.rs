@@ -5,6 +5,27 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impl;
+#[cfg(feature = "bytemuck")]
+mod bytemuck_impl;
+#[cfg(feature = "bytes")]
+mod bytes_impl;
 mod deque;
+mod error;
+#[cfg(feature = "std")]
+mod io_impl;
+mod iter;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "stable_hash")]
+mod stable_hash;
+#[cfg(feature = "zerocopy")]
+mod zerocopy_impl;
 
 pub use deque::*;
+pub use error::*;
+pub use iter::*;
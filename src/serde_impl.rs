@@ -0,0 +1,141 @@
+use crate::{ArrayDeque, ArrayDequePlain};
+use ::core::{fmt, marker::PhantomData};
+use ::serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{Error, SeqAccess, Visitor},
+    ser::SerializeSeq,
+};
+
+#[inline]
+fn serialize_slices<T: Serialize, S: Serializer>(
+    (first, second): (&[T], &[T]),
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut seq = serializer.serialize_seq(Some(first.len() + second.len()))?;
+    for v in first.iter().chain(second) {
+        seq.serialize_element(v)?;
+    }
+    seq.end()
+}
+
+/// Serializes as a sequence, in order.
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<T: Serialize, const CAP: usize> Serialize for ArrayDeque<T, CAP> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_slices(self.as_slices(), serializer)
+    }
+}
+
+/// Serializes as a sequence, in order.
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<T: Copy + Serialize, const CAP: usize> Serialize for ArrayDequePlain<T, CAP> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_slices(self.as_slices(), serializer)
+    }
+}
+
+macro_rules! impl_deserialize {
+    ($struct_name:ident $(< $($struct_gen:tt),* $(,)? >)?) => {
+        /// Deserializes from a sequence, returns an error if it has more than `CAP` elements.
+        #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+        impl<'de, T: $($($struct_gen +)*)? Deserialize<'de>, const CAP: usize> Deserialize<'de>
+            for $struct_name<T, CAP>
+        {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct SeqVisitor<T, const CAP: usize>(PhantomData<T>);
+
+                impl<'de, T: $($($struct_gen +)*)? Deserialize<'de>, const CAP: usize> Visitor<'de>
+                    for SeqVisitor<T, CAP>
+                {
+                    type Value = $struct_name<T, CAP>;
+
+                    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        write!(f, "a sequence of at most {CAP} elements")
+                    }
+
+                    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                        if let Some(len) = seq.size_hint()
+                            && len > CAP
+                        {
+                            return Err(A::Error::invalid_length(len, &self));
+                        }
+                        // on error the already pushed elements are dropped with `buf`
+                        let mut buf = $struct_name::new();
+                        while let Some(v) = seq.next_element()? {
+                            if buf.push_last(v).is_err() {
+                                return Err(A::Error::invalid_length(CAP + 1, &self));
+                            }
+                        }
+                        Ok(buf)
+                    }
+                }
+
+                deserializer.deserialize_seq(SeqVisitor(PhantomData))
+            }
+        }
+    };
+}
+
+impl_deserialize!(ArrayDeque);
+impl_deserialize!(ArrayDequePlain<Copy>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deque::tests::{DropCounter, dropped_count, take_dropped};
+
+    impl<'de> Deserialize<'de> for DropCounter {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            u8::deserialize(deserializer).map(|id| DropCounter(id.into()))
+        }
+    }
+
+    #[test]
+    fn test_serialize_wrapped() {
+        let mut buf: ArrayDeque<i32, 4> = ArrayDeque::new();
+        buf.push_last(2).unwrap();
+        buf.push_last(3).unwrap();
+        buf.push_first(1).unwrap();
+        assert!(!buf.is_contiguous());
+
+        let json = serde_json::to_string(&buf).unwrap();
+        assert_eq!(json, "[1,2,3]");
+
+        buf.linearize();
+        assert_eq!(serde_json::to_string(&buf).unwrap(), json);
+
+        let plain: ArrayDequePlain<i32, 4> = buf.iter().collect();
+        assert_eq!(serde_json::to_string(&plain).unwrap(), json);
+        let values: Vec<i32> = serde_json::from_str(&json).unwrap();
+        assert!(plain.iter().eq(&values));
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let buf: ArrayDeque<String, 4> = serde_json::from_str(r#"["a", "b", "c"]"#).unwrap();
+        assert!(buf.iter().eq(["a", "b", "c"]));
+
+        let plain: ArrayDequePlain<i32, 2> = serde_json::from_str("[1, 2]").unwrap();
+        assert!(plain.iter().eq(&[1, 2]));
+
+        let err = serde_json::from_str::<ArrayDequePlain<i32, 2>>("[1, 2, 3]").unwrap_err();
+        assert!(err.to_string().contains("at most 2 elements"));
+
+        let err = serde_json::from_str::<ArrayDeque<String, 4>>(r#"["a", "b", 3]"#);
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_deserialize_drop() {
+        assert!(serde_json::from_str::<ArrayDeque<DropCounter, 4>>("[1, 2, -1]").is_err());
+        assert_eq!(take_dropped(), [1, 2]);
+        assert!(serde_json::from_str::<ArrayDeque<DropCounter, 2>>("[1, 2, 3]").is_err());
+        assert_eq!(take_dropped(), [3, 1, 2]);
+        let buf = serde_json::from_str::<ArrayDeque<DropCounter, 2>>("[1, 2]").unwrap();
+        assert_eq!(dropped_count(), 0);
+        drop(buf);
+        assert_eq!(take_dropped(), [1, 2]);
+    }
+}
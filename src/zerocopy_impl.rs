@@ -0,0 +1,128 @@
+use crate::ArrayDequePlain;
+use ::core::{mem::MaybeUninit, slice};
+use ::zerocopy::{FromBytes, Immutable, IntoBytes};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "zerocopy")))]
+impl<T: Copy + IntoBytes + Immutable, const CAP: usize> ArrayDequePlain<T, CAP> {
+    /// Returns the bytes of the elements (in order), without copying.
+    ///
+    /// # Panics
+    ///
+    /// Must be contiguous (`as_slices().1` is empty). If it's not, use `linearize()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDequePlain;
+    ///
+    /// let mut buf: ArrayDequePlain<u16, 4> = ArrayDequePlain::new();
+    /// buf.push_last(0x0102).unwrap();
+    /// buf.push_last(0x0304).unwrap();
+    ///
+    /// assert_eq!(buf.as_byte_slice().len(), 4);
+    /// assert_eq!(buf.as_byte_slice()[..2], 0x0102u16.to_ne_bytes());
+    /// ```
+    #[inline]
+    pub fn as_byte_slice(&self) -> &[u8] {
+        let (first, second) = self.as_slices();
+        assert!(second.is_empty(), "deque must be contiguous");
+        first.as_bytes()
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "zerocopy")))]
+impl<T: Copy + IntoBytes + FromBytes, const CAP: usize> ArrayDequePlain<T, CAP> {
+    /// Returns the mutable bytes of the elements (in order), without copying.
+    ///
+    /// # Panics
+    ///
+    /// Must be contiguous (`as_slices().1` is empty). If it's not, use `linearize()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDequePlain;
+    ///
+    /// let mut buf: ArrayDequePlain<u16, 4> = ArrayDequePlain::new();
+    /// buf.push_last(0).unwrap();
+    /// buf.as_byte_slice_mut().copy_from_slice(&7u16.to_ne_bytes());
+    ///
+    /// assert_eq!(buf.first(), Some(&7));
+    /// ```
+    #[inline]
+    pub fn as_byte_slice_mut(&mut self) -> &mut [u8] {
+        let (first, second) = self.as_mut_slices();
+        assert!(second.is_empty(), "deque must be contiguous");
+        first.as_mut_bytes()
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "zerocopy")))]
+impl<const CAP: usize> ArrayDequePlain<u8, CAP> {
+    /// Removes `size_of::<H>()` bytes from the start, and reads them as `H`.
+    /// Returns `None` (and removes nothing) if there are not enough bytes.
+    ///
+    /// Works for a non-contiguous deque too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_buf::ArrayDequePlain;
+    ///
+    /// let mut buf: ArrayDequePlain<u8, 8> = ArrayDequePlain::new();
+    /// buf.extend_from_slice(&0x0102_0304u32.to_be_bytes()).unwrap();
+    /// buf.push_last(5).unwrap();
+    ///
+    /// let header: [u8; 4] = buf.read_header().unwrap();
+    /// assert_eq!(u32::from_be_bytes(header), 0x0102_0304);
+    /// assert_eq!(buf.read_header::<u16>(), None);
+    /// assert_eq!(buf.len(), 1);
+    /// ```
+    #[inline]
+    pub fn read_header<H: FromBytes>(&mut self) -> Option<H> {
+        let size = size_of::<H>();
+        if self.len() < size {
+            return None;
+        }
+        let mut header = MaybeUninit::<H>::zeroed();
+        // zeroed, so all the bytes are initialized
+        let bytes = unsafe { slice::from_raw_parts_mut(header.as_mut_ptr().cast::<u8>(), size) };
+        self.pop_first_n(bytes);
+        // `H: FromBytes` is valid for any bytes
+        Some(unsafe { header.assume_init() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_header_wrapped() {
+        let mut buf: ArrayDequePlain<u8, 8> = ArrayDequePlain::new();
+        buf.extend_from_slice(&[0; 6]).unwrap();
+        while buf.pop_first().is_some() {}
+        buf.extend_from_slice(&[1, 2, 3, 4, 5]).unwrap();
+        assert!(!buf.is_contiguous());
+
+        assert_eq!(buf.read_header::<[u8; 3]>(), Some([1, 2, 3]));
+        assert_eq!(buf.read_header::<[u8; 3]>(), None);
+        assert_eq!(buf.read_header::<[u8; 2]>(), Some([4, 5]));
+        assert!(buf.is_empty());
+        assert_eq!(buf.read_header::<()>(), Some(()));
+    }
+
+    #[test]
+    fn test_as_byte_slice() {
+        let mut buf: ArrayDequePlain<u32, 4> = ArrayDequePlain::new();
+        buf.push_last(2).unwrap();
+        buf.push_first(1).unwrap();
+        assert!(std::panic::catch_unwind(|| buf.as_byte_slice().len()).is_err());
+
+        buf.linearize();
+        let bytes: Vec<u8> = [1u32, 2].iter().flat_map(|v| v.to_ne_bytes()).collect();
+        assert_eq!(buf.as_byte_slice(), bytes);
+        buf.as_byte_slice_mut()[4..].copy_from_slice(&9u32.to_ne_bytes());
+        assert!(buf.iter().eq(&[1, 9]));
+    }
+}